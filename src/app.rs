@@ -0,0 +1,2508 @@
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::events::SubmitEvent;
+use yew::prelude::*;
+use yew_hooks::{use_debounce_effect_with_deps, use_event_with_window, use_interval};
+
+use crate::browser;
+use crate::components::chart::{LineChart, Marker, Series};
+use crate::components::comparison_table::ComparisonTable;
+use crate::components::flight_canvas::FlightCanvas;
+use crate::components::help_tip::HelpTip;
+use crate::components::lateral_table::LateralTable;
+use crate::components::range_card::RangeCard;
+use crate::components::range_table::RangeTable;
+use crate::input::{has_bad_input, input_target, parse_input, parse_select, textarea_value};
+
+use enigma::atmosphere::{
+    air_density, pressure_for_density, temperature_at, STANDARD_PRESSURE, STANDARD_TEMPERATURE,
+};
+use enigma::ballistics::{
+    crosswind, drag_loss_per_step, eotvos_lift, headwind_range_cost, height_above_sight_line,
+    height_in_frame, impact, kinetic_energy, landing_point, lerp_points, max_ordinate,
+    miss_distance, point_at_range, range_below_energy, simulate, simulate_to_range,
+    supersonic_range, transonic_range, truncated, Integrator, ShotParams, TrajectoryPoint, Vector3,
+    MAX_DRAG_LOSS_PER_STEP, MAX_TIME_STEP, MIN_TIME_STEP, TIME_STEP,
+};
+use enigma::cartridge::{self, CARTRIDGES};
+use enigma::dispersion::{simulate_group, Spread};
+use enigma::drag::{
+    convert_bc, drag_curve, form_factor, sectional_density, BcBand, BcBands, DragModel, DragTable,
+    MAX_BC_BANDS, MAX_DRAG_POINTS,
+};
+use enigma::error::BallisticError;
+use enigma::help::{self, Help};
+use enigma::profile::{Presets, ShotProfile};
+use enigma::scope::{clicks, correction, inclined_drop, lead};
+use enigma::shortcuts::{shortcut, Shortcut, BINDINGS};
+use enigma::solver::{danger_space, mpbr, optimal_angle, solve_elevation, zero};
+use enigma::spin::{
+    aerodynamic_jump, muzzle_spin_rate, spin_drift, spin_rate, TwistDirection, MARGINAL_STABILITY,
+    UNSTABLE_STABILITY,
+};
+use enigma::state::{GroupSettings, ShotAction, ShotState, TableLayout, Target};
+use enigma::table::{
+    bracket, compare_tables, in_frame, lag_times, lateral_table, range_card, range_table, to_csv,
+    to_tsv, wind_holds, DROP_SIGN,
+};
+use enigma::timestep::Accumulator;
+use enigma::units::{foot_pounds, AngleUnit, Quantity, UnitSystem, METERS_PER_FOOT};
+use enigma::validation::validate;
+use enigma::wind::{WindSample, MAX_WIND_SAMPLES};
+use enigma::worker::{TrajectoryRequest, TrajectoryResponse, TrajectoryWorker, WORKER_PATH};
+use gloo_timers::callback::Timeout;
+use gloo_worker::Spawnable;
+
+/// Where the last-used inputs are remembered between visits.
+const STORAGE_KEY: &str = "ballistic-calc.profile";
+
+/// Where named presets are kept.
+const PRESETS_KEY: &str = "ballistic-calc.presets";
+
+/// How long to wait for the worker before computing on the main thread.
+const WORKER_TIMEOUT_MS: u32 = 2000;
+
+/// How far up the Mach scale the drag chart goes, and in how many steps.
+const DRAG_CHART_MAX_MACH: f64 = 5.0;
+const DRAG_CHART_SAMPLES: usize = 100;
+
+/// How far out to follow the shot looking for where it goes subsonic.
+const SUPERSONIC_SEARCH_RANGE: f64 = 5000.0;
+
+/// How long the form has to sit still before the tables and charts are
+/// worked out again.
+const SETTLE_MS: u32 = 300;
+
+/// What the tables and charts are worked out from.
+#[derive(Clone, Copy, PartialEq)]
+struct Workload {
+    params: ShotParams,
+    sight_height: f64,
+    tables: TableLayout,
+    target_range: f64,
+    load_b: Option<ShotProfile>,
+}
+
+#[function_component]
+pub fn BallisticCalculator() -> Html {
+    let state = use_reducer(ShotState::default);
+    let params = state.params();
+    let units = state.unit_system;
+    let drag_table_error = use_state(|| None::<String>);
+    let zero_result = use_state(|| None);
+    let mpbr_result = use_state(|| None);
+    let max_range_result = use_state(|| None);
+    let group_result = use_state(|| None);
+    let solve_error = use_state(|| None::<BallisticError>);
+    let elevation_ref = use_node_ref();
+    let latitude_ref = use_node_ref();
+    let location_error = use_state(|| false);
+    // Set when the setup holds something a link can't carry.
+    let link_unavailable = use_state(|| false);
+    let ballistic_coefficient_ref = use_node_ref();
+    let range_table_ref = use_node_ref();
+    // Set when the clipboard refused the table and it was selected instead.
+    let table_selected = use_state(|| false);
+    let altitude_ref = use_node_ref();
+    let temperature_ref = use_node_ref();
+    let pressure_ref = use_node_ref();
+    let humidity_ref = use_node_ref();
+    let density_altitude_ref = use_node_ref();
+    // Set when the density altitude was typed in, so the pressure it implies
+    // doesn't write a rounded value back over what's being typed.
+    let density_altitude_typed = use_mut_ref(|| false);
+    let profile_error = use_state(|| None::<String>);
+    let invalid_field = use_state(|| None::<String>);
+    let presets = use_state(|| {
+        browser::load(PRESETS_KEY)
+            .and_then(|json| Presets::from_json(&json).ok())
+            .unwrap_or_default()
+    });
+    let preset_name = use_state(String::new);
+    let selected_preset = use_state(|| None::<String>);
+    // The whole flight is computed on submit and played back by moving a
+    // playhead along it, blending towards the next point by `alpha`.
+    let flight = use_state(Vec::<TrajectoryPoint>::new);
+    // The step the flight was computed with, which playback advances by.
+    let flight_step = use_state(|| TIME_STEP);
+    let playhead = use_state(|| 0usize);
+    let alpha = use_state(|| 0.0);
+    let computing = use_state(|| false);
+    // Ids of the last request sent and the one still awaiting an answer.
+    let next_request = use_mut_ref(|| 0u32);
+    let pending_request = use_mut_ref(|| None::<u32>);
+
+    let on_wind_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetWind(units.to_si(Quantity::Velocity, value)));
+            }
+        })
+    };
+
+    let on_wind_direction_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetWindDirection(value));
+            }
+        })
+    };
+
+    let on_add_wind_sample = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::AddWindSample))
+    };
+
+    let on_remove_wind_sample = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::RemoveWindSample))
+    };
+
+    let on_wind_sample_input = |index: usize, field: fn(&mut WindSample, f64, UnitSystem)| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let (Some(value), Some(mut sample)) =
+                (parse_input(&e), state.wind_samples.get(index).copied())
+            {
+                field(&mut sample, value, units);
+                state.dispatch(ShotAction::SetWindSample(index, sample));
+            }
+        })
+    };
+
+    let on_elevation_input = {
+        let state = state.clone();
+        let elevation_unit = state.elevation_unit;
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetElevation(elevation_unit.to_degrees(value)));
+            }
+        })
+    };
+
+    // Shows `degrees` in the elevation field, in whichever unit it's set to.
+    let show_elevation = {
+        let elevation_ref = elevation_ref.clone();
+        Callback::from(move |(degrees, unit): (f64, AngleUnit)| {
+            if let Some(input) = elevation_ref.cast::<HtmlInputElement>() {
+                input.set_value(&format!("{:.4}", unit.from_degrees(degrees)));
+            }
+        })
+    };
+
+    let on_click_value_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetClickValue(value));
+            }
+        })
+    };
+
+    let on_click_unit_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let Some(unit) = parse_select(&e) {
+                state.dispatch(ShotAction::SetClickUnit(unit));
+            }
+        })
+    };
+
+    let on_elevation_unit_change = {
+        let state = state.clone();
+        let show_elevation = show_elevation.clone();
+        let elevation = params.elevation;
+        Callback::from(move |e: Event| {
+            if let Some(unit) = parse_select(&e) {
+                show_elevation.emit((elevation, unit));
+                state.dispatch(ShotAction::SetElevationUnit(unit));
+            }
+        })
+    };
+
+    let on_caliber_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetCaliber(units.to_si(Quantity::Length, value)));
+            }
+        })
+    };
+
+    let on_ballistic_coefficient_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetBallisticCoefficient(value));
+            }
+        })
+    };
+
+    let on_converter_bc_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                state.dispatch(ShotAction::SetConverterBc(value.max(0.0)));
+            }
+        })
+    };
+
+    let on_converter_model_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let Some(model) = parse_select(&e) {
+                state.dispatch(ShotAction::SetConverterModel(model));
+            }
+        })
+    };
+
+    // The converted BC is matched at the muzzle, where it matters most.
+    let converted_model = if state.converter_model == DragModel::G1 {
+        DragModel::G7
+    } else {
+        DragModel::G1
+    };
+    let converted_bc = Some(state.converter_bc)
+        .filter(|&bc| bc > 0.0)
+        .and_then(|bc| {
+            let mach = params.effective_muzzle_velocity() / params.speed_of_sound_at(0.0);
+            convert_bc(bc, state.converter_model, converted_model, mach)
+        });
+
+    let on_use_converted_bc = {
+        let state = state.clone();
+        let ballistic_coefficient_ref = ballistic_coefficient_ref.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(bc) = converted_bc else {
+                return;
+            };
+            if let Some(input) = ballistic_coefficient_ref.cast::<HtmlInputElement>() {
+                input.set_value(&format!("{:.3}", bc));
+            }
+            state.dispatch(ShotAction::SetDragModel(converted_model));
+            state.dispatch(ShotAction::SetBallisticCoefficient(bc));
+        })
+    };
+
+    let on_add_bc_band = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::AddBcBand))
+    };
+
+    let on_remove_bc_band = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::RemoveBcBand))
+    };
+
+    let on_bc_band_input = |index: usize, field: fn(&mut BcBand, f64, UnitSystem)| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let (Some(value), Some(mut band)) =
+                (parse_input(&e), state.bc_bands.get(index).copied())
+            {
+                field(&mut band, value, units);
+                state.dispatch(ShotAction::SetBcBand(index, band));
+            }
+        })
+    };
+
+    let on_muzzle_velocity_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetMuzzleVelocity(
+                        units.to_si(Quantity::Velocity, value),
+                    ));
+                }
+            }
+        })
+    };
+
+    let on_drag_model_change = {
+        let state = state.clone();
+        Callback::from(
+            move |e: Event| match (parse_select::<String>(&e), state.custom_drag) {
+                (Some(label), Some(table)) if label == "Custom" => {
+                    state.dispatch(ShotAction::SetDragModel(DragModel::Custom(table)));
+                }
+                (Some(label), _) => {
+                    if let Ok(model) = label.parse() {
+                        state.dispatch(ShotAction::SetDragModel(model));
+                    }
+                }
+                _ => {}
+            },
+        )
+    };
+
+    // Switches to a pasted or uploaded drag table, or says what's wrong with it.
+    let use_drag_table = {
+        let state = state.clone();
+        let drag_table_error = drag_table_error.clone();
+        Callback::from(move |csv: String| match DragTable::from_csv(&csv) {
+            Ok(table) => {
+                state.dispatch(ShotAction::UseDragTable(table));
+                drag_table_error.set(None);
+            }
+            Err(err) => drag_table_error.set(Some(err.to_string())),
+        })
+    };
+
+    let on_drag_table_paste = {
+        let use_drag_table = use_drag_table.clone();
+        Callback::from(move |e: Event| {
+            if let Some(csv) = textarea_value(&e).filter(|csv| !csv.trim().is_empty()) {
+                use_drag_table.emit(csv);
+            }
+        })
+    };
+
+    let on_drag_table_upload = {
+        let drag_table_error = drag_table_error.clone();
+        Callback::from(move |e: Event| {
+            let Some(input) = input_target(&e) else {
+                return;
+            };
+            let use_drag_table = use_drag_table.clone();
+            let drag_table_error = drag_table_error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let text = browser::read_text(&input).await;
+                input.set_value("");
+                match text {
+                    Ok(csv) => use_drag_table.emit(csv),
+                    Err(_) => drag_table_error.set(Some("could not read the selected file".into())),
+                }
+            });
+        })
+    };
+
+    let on_latitude_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if (-90.0..=90.0).contains(&value) {
+                    state.dispatch(ShotAction::SetLatitude(value));
+                }
+            }
+        })
+    };
+
+    // Asks the browser where we are without holding up the form; if it won't
+    // say, the latitude is left for typing in.
+    let on_use_location = {
+        let state = state.clone();
+        let latitude_ref = latitude_ref.clone();
+        let location_error = location_error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let state = state.clone();
+            let latitude_ref = latitude_ref.clone();
+            let location_error = location_error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match browser::current_latitude().await {
+                    Ok(latitude) => {
+                        if let Some(input) = latitude_ref.cast::<HtmlInputElement>() {
+                            input.set_value(&format!("{:.4}", latitude));
+                        }
+                        state.dispatch(ShotAction::SetLatitude(latitude));
+                        location_error.set(false);
+                    }
+                    Err(_) => location_error.set(true),
+                }
+            });
+        })
+    };
+
+    let on_azimuth_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetAzimuth(value));
+            }
+        })
+    };
+
+    let on_twist_rate_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetTwistRate(value));
+                }
+            }
+        })
+    };
+
+    let on_twist_direction_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let Some(value) = parse_select(&e) {
+                state.dispatch(ShotAction::SetTwistDirection(value));
+            }
+        })
+    };
+
+    let on_bullet_length_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetBulletLength(
+                        units.to_si(Quantity::Length, value),
+                    ));
+                }
+            }
+        })
+    };
+
+    let on_mass_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetMass(units.to_si(Quantity::Mass, value)));
+                }
+            }
+        })
+    };
+
+    let on_altitude_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetAltitude(
+                    units.to_si(Quantity::Distance, value),
+                ));
+            }
+        })
+    };
+
+    let on_temperature_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetTemperature(value));
+            }
+        })
+    };
+
+    let on_pressure_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetPressure(value));
+                }
+            }
+        })
+    };
+
+    // Density altitude is quoted in feet by nearly everyone using imperial.
+    let density_altitude_per_unit = if units == UnitSystem::Imperial {
+        METERS_PER_FOOT
+    } else {
+        1.0
+    };
+
+    // Finds the sea-level pressure that, with the other conditions kept,
+    // gives the same air as a standard day at the entered density altitude.
+    let on_density_altitude_input = {
+        let state = state.clone();
+        let pressure_ref = pressure_ref.clone();
+        let density_altitude_typed = density_altitude_typed.clone();
+        Callback::from(move |e: InputEvent| {
+            let Some(value) = parse_input::<f64>(&e) else {
+                return;
+            };
+            let density = air_density(
+                value * density_altitude_per_unit,
+                STANDARD_TEMPERATURE,
+                STANDARD_PRESSURE,
+                0.0,
+            );
+            let pressure = pressure_for_density(
+                density,
+                params.altitude,
+                params.temperature,
+                params.humidity,
+            );
+            if pressure > 0.0 && pressure.is_finite() {
+                if let Some(input) = pressure_ref.cast::<HtmlInputElement>() {
+                    input.set_value(&format!("{:.2}", pressure));
+                }
+                *density_altitude_typed.borrow_mut() = true;
+                state.dispatch(ShotAction::SetPressure(pressure));
+            }
+        })
+    };
+
+    let on_standard_conditions = {
+        let state = state.clone();
+        let fields = [
+            (altitude_ref.clone(), 0.0),
+            (temperature_ref.clone(), STANDARD_TEMPERATURE),
+            (pressure_ref.clone(), STANDARD_PRESSURE),
+            (humidity_ref.clone(), 0.0),
+        ];
+        Callback::from(move |_: MouseEvent| {
+            for (field, value) in &fields {
+                if let Some(input) = field.cast::<HtmlInputElement>() {
+                    input.set_value(&value.to_string());
+                }
+            }
+            state.dispatch(ShotAction::SetStandardConditions);
+        })
+    };
+
+    let on_humidity_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if (0.0..=100.0).contains(&value) {
+                    state.dispatch(ShotAction::SetHumidity(value));
+                }
+            }
+        })
+    };
+
+    let on_velocity_temp_sensitivity_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetVelocityTempSensitivity(
+                    units.to_si(Quantity::Velocity, value),
+                ));
+            }
+        })
+    };
+
+    let on_reference_temperature_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetReferenceTemperature(value));
+            }
+        })
+    };
+
+    let on_integrator_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let Some(value) = parse_select(&e) {
+                state.dispatch(ShotAction::SetIntegrator(value));
+            }
+        })
+    };
+
+    let on_drag_enabled_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let Some(input) = input_target(&e) {
+                state.dispatch(ShotAction::SetDragEnabled(input.checked()));
+            }
+        })
+    };
+
+    let on_magnus_enabled_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let Some(input) = input_target(&e) {
+                state.dispatch(ShotAction::SetMagnusEnabled(input.checked()));
+            }
+        })
+    };
+
+    let on_time_step_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetTimeStep(value));
+            }
+        })
+    };
+
+    let on_max_flight_time_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetMaxFlightTime(value));
+                }
+            }
+        })
+    };
+
+    let on_max_range_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetMaxRange(
+                        units.to_si(Quantity::Distance, value),
+                    ));
+                }
+            }
+        })
+    };
+
+    let on_eotvos_enabled_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let Some(input) = input_target(&e) {
+                state.dispatch(ShotAction::SetEotvosEnabled(input.checked()));
+            }
+        })
+    };
+
+    let on_target_range_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetTarget(Target {
+                        range: units.to_si(Quantity::Distance, value),
+                        ..state.target
+                    }));
+                }
+            }
+        })
+    };
+
+    let on_target_height_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetTarget(Target {
+                    height: units.to_si(Quantity::Distance, value),
+                    ..state.target
+                }));
+            }
+        })
+    };
+
+    let on_target_speed_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value >= 0.0 {
+                    state.dispatch(ShotAction::SetTarget(Target {
+                        speed: units.to_si(Quantity::Velocity, value),
+                        ..state.target
+                    }));
+                }
+            }
+        })
+    };
+
+    let on_target_angle_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetTarget(Target {
+                    angle: value,
+                    ..state.target
+                }));
+            }
+        })
+    };
+
+    let on_hit_radius_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e).filter(|value| *value > 0.0) {
+                state.dispatch(ShotAction::SetTarget(Target {
+                    hit_radius: units.to_si(Quantity::Length, value),
+                    ..state.target
+                }));
+            }
+        })
+    };
+
+    let on_target_size_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e).filter(|value| *value > 0.0) {
+                state.dispatch(ShotAction::SetTarget(Target {
+                    size: units.to_si(Quantity::Length, value),
+                    ..state.target
+                }));
+            }
+        })
+    };
+
+    let on_min_energy_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e).filter(|value| *value >= 0.0) {
+                state.dispatch(ShotAction::SetTarget(Target {
+                    min_energy: value,
+                    ..state.target
+                }));
+            }
+        })
+    };
+
+    let on_look_angle_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetTarget(Target {
+                    look_angle: value,
+                    ..state.target
+                }));
+            }
+        })
+    };
+
+    let on_zero_distance_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetZeroDistance(
+                        units.to_si(Quantity::Distance, value),
+                    ));
+                }
+            }
+        })
+    };
+
+    let on_sight_height_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value >= 0.0 {
+                    state.dispatch(ShotAction::SetSightHeight(
+                        units.to_si(Quantity::Length, value),
+                    ));
+                }
+            }
+        })
+    };
+
+    let on_vital_zone_diameter_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetTarget(Target {
+                        vital_zone: units.to_si(Quantity::Length, value),
+                        ..state.target
+                    }));
+                }
+            }
+        })
+    };
+
+    let on_table_step_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetTableLayout(TableLayout {
+                        step: units.to_si(Quantity::Distance, value),
+                        ..state.tables
+                    }));
+                }
+            }
+        })
+    };
+
+    let on_table_max_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetTableLayout(TableLayout {
+                        max: units.to_si(Quantity::Distance, value),
+                        ..state.tables
+                    }));
+                }
+            }
+        })
+    };
+
+    let on_reference_wind_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e).filter(|value| *value >= 0.0) {
+                state.dispatch(ShotAction::SetTableLayout(TableLayout {
+                    reference_wind: units.to_si(Quantity::Velocity, value),
+                    ..state.tables
+                }));
+            }
+        })
+    };
+
+    let on_bracket_center_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e).filter(|value| *value >= 0.0) {
+                state.dispatch(ShotAction::SetTableLayout(TableLayout {
+                    bracket_center: units.to_si(Quantity::Distance, value),
+                    ..state.tables
+                }));
+            }
+        })
+    };
+
+    let on_bracket_step_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetTableLayout(TableLayout {
+                        bracket_step: units.to_si(Quantity::Distance, value),
+                        ..state.tables
+                    }));
+                }
+            }
+        })
+    };
+
+    // Every input event bubbles up to the form, so flag unreadable text here
+    // once instead of in each field's handler.
+    let on_form_input = {
+        let invalid_field = invalid_field.clone();
+        Callback::from(move |e: InputEvent| {
+            let Some(input) = input_target(&e) else {
+                return;
+            };
+            let field = input.placeholder();
+            if has_bad_input(&e) {
+                invalid_field.set(Some(field));
+            } else if invalid_field.as_deref() == Some(field.as_str()) {
+                invalid_field.set(None);
+            }
+        })
+    };
+
+    let on_unit_toggle = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(ShotAction::SetUnitSystem(units.toggle()))
+        })
+    };
+
+    let on_frame_toggle = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| {
+            state.dispatch(ShotAction::SetFrame(state.frame.toggle()))
+        })
+    };
+
+    let on_time_scale_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e) {
+                if value > 0.0 {
+                    state.dispatch(ShotAction::SetTimeScale(value));
+                }
+            }
+        })
+    };
+
+    let toggle_pause = {
+        let state = state.clone();
+        Callback::from(move |()| state.dispatch(ShotAction::TogglePause))
+    };
+    let on_pause_toggle = toggle_pause.reform(|_: MouseEvent| ());
+
+    let errors = validate(&params).err().unwrap_or_default();
+
+    // Each table and chart integrates the shot again, which is too slow to
+    // redo on every keystroke, so they follow a copy of their inputs that
+    // only catches up once the form has been still for a moment.
+    let workload = Workload {
+        params,
+        sight_height: state.sight_height,
+        tables: state.tables,
+        target_range: state.target.range,
+        load_b: state.load_b,
+    };
+    let settled = use_state(|| workload);
+    {
+        let settled = settled.clone();
+        use_debounce_effect_with_deps(move || settled.set(workload), SETTLE_MS, workload);
+    }
+    let settling = *settled != workload;
+
+    // Invalid shots are listed from `errors` and just draw nothing.
+    let trajectory = use_memo(
+        |params| simulate(params).unwrap_or_default(),
+        settled.params,
+    );
+    let tables = use_memo(
+        |(params, step, max, sight_height)| {
+            let points = simulate_to_range(params, *max);
+            (
+                range_table(&points, *step, *max, params.mass, *sight_height),
+                lateral_table(
+                    &points,
+                    *step,
+                    *max,
+                    params.stability(),
+                    params.twist_direction,
+                ),
+            )
+        },
+        (
+            settled.params,
+            settled.tables.step,
+            settled.tables.max,
+            settled.sight_height,
+        ),
+    );
+    let wind_hold_rows = use_memo(
+        |(params, speed, step, max)| {
+            if *speed > 0.0 {
+                wind_holds(params, *speed, *step, *max)
+            } else {
+                Vec::new()
+            }
+        },
+        (
+            settled.params,
+            settled.tables.reference_wind,
+            settled.tables.step,
+            settled.tables.max,
+        ),
+    );
+    let bracket_rows = use_memo(
+        |(params, center, step, sight_height)| {
+            if *center > 0.0 {
+                let points = simulate_to_range(params, center + 2.0 * step);
+                bracket(&points, *center, *step, params.mass, *sight_height)
+            } else {
+                Vec::new()
+            }
+        },
+        (
+            settled.params,
+            settled.tables.bracket_center,
+            settled.tables.bracket_step,
+            settled.sight_height,
+        ),
+    );
+    let lag_rows = use_memo(
+        |(params, step, max)| lag_times(params, *step, *max),
+        (settled.params, settled.tables.step, settled.tables.max),
+    );
+    let trajectory_b = use_memo(
+        |load| load.map(|load| simulate(&load.shot).unwrap_or_default()),
+        settled.load_b,
+    );
+    let table_b = use_memo(
+        |(load, step, max)| {
+            load.map(|load| {
+                let points = simulate_to_range(&load.shot, *max);
+                range_table(&points, *step, *max, load.shot.mass, load.sight_height)
+            })
+        },
+        (settled.load_b, settled.tables.step, settled.tables.max),
+    );
+    let supersonic = use_memo(
+        |params| {
+            let points = simulate_to_range(params, SUPERSONIC_SEARCH_RANGE);
+            supersonic_range(&points, params)
+        },
+        settled.params,
+    );
+    let range_cost = use_memo(|params| headwind_range_cost(params).ok(), settled.params);
+    let eotvos = use_memo(
+        |(params, range)| eotvos_lift(params, *range),
+        (settled.params, settled.target_range),
+    );
+    let downrange = use_memo(
+        |(params, range)| simulate_to_range(params, *range),
+        (settled.params, settled.target_range),
+    );
+
+    let on_solve = {
+        let state = state.clone();
+        let solve_error = solve_error.clone();
+        let show_elevation = show_elevation.clone();
+        let elevation_unit = state.elevation_unit;
+        let target = state.target;
+        Callback::from(move |_: MouseEvent| {
+            match solve_elevation(&params, target.range, target.height) {
+                Ok(solution) => {
+                    show_elevation.emit((solution, elevation_unit));
+                    state.dispatch(ShotAction::SetElevation(solution));
+                    solve_error.set(None);
+                }
+                Err(err) => solve_error.set(Some(err)),
+            }
+        })
+    };
+
+    let on_zero = {
+        let state = state.clone();
+        let zero_result = zero_result.clone();
+        let show_elevation = show_elevation.clone();
+        let elevation_unit = state.elevation_unit;
+        let zero_distance = state.zero_distance;
+        let sight_height = state.sight_height;
+        Callback::from(move |_: MouseEvent| {
+            let result = zero(&params, zero_distance, sight_height);
+            if let Some(result) = result {
+                show_elevation.emit((result.elevation, elevation_unit));
+                state.dispatch(ShotAction::SetElevation(result.elevation));
+            }
+            zero_result.set(Some(result));
+        })
+    };
+
+    let on_mpbr = {
+        let state = state.clone();
+        let mpbr_result = mpbr_result.clone();
+        let show_elevation = show_elevation.clone();
+        let elevation_unit = state.elevation_unit;
+        let vital_radius = state.target.vital_zone / 2.0;
+        let sight_height = state.sight_height;
+        Callback::from(move |_: MouseEvent| {
+            let result = mpbr(&params, vital_radius, sight_height);
+            if let Some(result) = result {
+                show_elevation.emit((result.elevation, elevation_unit));
+                state.dispatch(ShotAction::SetElevation(result.elevation));
+            }
+            mpbr_result.set(Some(result));
+        })
+    };
+
+    let on_max_range = {
+        let max_range_result = max_range_result.clone();
+        Callback::from(move |_: MouseEvent| max_range_result.set(Some(optimal_angle(&params))))
+    };
+
+    let on_group_shots_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetGroup(GroupSettings {
+                    shots: value,
+                    ..state.group
+                }));
+            }
+        })
+    };
+
+    let on_velocity_sd_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input::<f64>(&e).filter(|value| *value >= 0.0) {
+                let spread = Spread {
+                    velocity_sd: units.to_si(Quantity::Velocity, value),
+                    ..state.group.spread
+                };
+                state.dispatch(ShotAction::SetGroup(GroupSettings {
+                    spread,
+                    ..state.group
+                }));
+            }
+        })
+    };
+
+    let on_gust_min_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                let spread = Spread {
+                    wind_min: units.to_si(Quantity::Velocity, value),
+                    ..state.group.spread
+                };
+                state.dispatch(ShotAction::SetGroup(GroupSettings {
+                    spread,
+                    ..state.group
+                }));
+            }
+        })
+    };
+
+    let on_gust_max_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                let spread = Spread {
+                    wind_max: units.to_si(Quantity::Velocity, value),
+                    ..state.group.spread
+                };
+                state.dispatch(ShotAction::SetGroup(GroupSettings {
+                    spread,
+                    ..state.group
+                }));
+            }
+        })
+    };
+
+    let on_group_seed_input = {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = parse_input(&e) {
+                state.dispatch(ShotAction::SetGroup(GroupSettings {
+                    seed: value,
+                    ..state.group
+                }));
+            }
+        })
+    };
+
+    let on_simulate_group = {
+        let group_result = group_result.clone();
+        let range = state.target.range;
+        let GroupSettings {
+            shots,
+            spread,
+            seed,
+        } = state.group;
+        Callback::from(move |_: MouseEvent| {
+            group_result.set(Some(simulate_group(&params, range, spread, shots, seed)));
+        })
+    };
+
+    // Starts playing a computed flight, unless a newer submit has replaced
+    // the request it answers.
+    let start_flight = {
+        let flight = flight.clone();
+        let flight_step = flight_step.clone();
+        let playhead = playhead.clone();
+        let alpha = alpha.clone();
+        let state = state.clone();
+        let computing = computing.clone();
+        let pending_request = pending_request.clone();
+        Callback::from(move |response: TrajectoryResponse| {
+            let mut pending = pending_request.borrow_mut();
+            if *pending != Some(response.id) {
+                return;
+            }
+            *pending = None;
+            flight.set(response.points);
+            flight_step.set(response.time_step);
+            playhead.set(0);
+            alpha.set(0.0);
+            state.dispatch(ShotAction::SetRunning(true));
+            computing.set(false);
+        })
+    };
+
+    let worker = {
+        let start_flight = start_flight.clone();
+        use_memo(
+            move |_| {
+                let supported =
+                    js_sys::Reflect::has(&js_sys::global(), &"Worker".into()).unwrap_or(false);
+                supported.then(|| {
+                    TrajectoryWorker::spawner()
+                        .callback(move |response| start_flight.emit(response))
+                        .spawn(WORKER_PATH)
+                })
+            },
+            (),
+        )
+    };
+
+    let fire = {
+        let state = state.clone();
+        let unreadable = invalid_field.is_some();
+        let computing = computing.clone();
+        let pending_request = pending_request.clone();
+        let next_request = next_request.clone();
+        Callback::from(move |()| {
+            // Whatever is wrong is already listed above the form; firing
+            // anyway would animate a shot nobody entered.
+            let Ok(params) = state.shot_to_fire() else {
+                return;
+            };
+            if unreadable {
+                return;
+            }
+            state.dispatch(ShotAction::Fire);
+            let id = {
+                let mut next = next_request.borrow_mut();
+                *next += 1;
+                *next
+            };
+            *pending_request.borrow_mut() = Some(id);
+            let request = TrajectoryRequest { id, params };
+
+            match worker.as_ref() {
+                Some(worker) => {
+                    computing.set(true);
+                    worker.send(request);
+                    // A worker whose script failed to load never answers, so
+                    // compute it here if it's still outstanding after a while.
+                    let start_flight = start_flight.clone();
+                    Timeout::new(WORKER_TIMEOUT_MS, move || start_flight.emit(request.run()))
+                        .forget();
+                }
+                None => start_flight.emit(request.run()),
+            }
+        })
+    };
+
+    let on_submit = fire.reform(|e: SubmitEvent| e.prevent_default());
+
+    // Moves the playhead `steps` physics steps along the flight, stopping at
+    // the landing point.
+    let advance = {
+        let flight = flight.clone();
+        let playhead = playhead.clone();
+        Callback::from(move |steps: usize| {
+            let last = flight.len().saturating_sub(1);
+            if steps > 0 && *playhead < last {
+                playhead.set((*playhead + steps).min(last));
+            }
+        })
+    };
+
+    let on_step = {
+        let advance = advance.clone();
+        let alpha = alpha.clone();
+        Callback::from(move |_: MouseEvent| {
+            advance.emit(1);
+            alpha.set(0.0);
+        })
+    };
+
+    let running = state.running;
+    let time_scale_value = state.time_scale;
+    let flight_step_value = *flight_step;
+    let alpha_clone = alpha.clone();
+    // Wall-clock time of the previous tick, in seconds, and the physics time
+    // owed since then.
+    let clock = use_mut_ref(|| (None::<f64>, Accumulator::default()));
+    let clock_clone = clock.clone();
+
+    use_interval(
+        move || {
+            let mut clock = clock_clone.borrow_mut();
+            let (last_tick, accumulator) = &mut *clock;
+            if !running {
+                // Don't count paused time once playback resumes.
+                *last_tick = None;
+                return;
+            }
+            let now = js_sys::Date::now() / 1000.0;
+            let frame = last_tick.map_or(0.0, |last| now - last);
+            *last_tick = Some(now);
+
+            accumulator.set_dt(flight_step_value);
+            advance.emit(accumulator.advance(frame * time_scale_value));
+            alpha_clone.set(accumulator.alpha());
+        },
+        10,
+    );
+
+    let stability = params.stability();
+    let current = flight.get(*playhead).copied().unwrap_or_default();
+    let landing = (flight.len() > 1 && *playhead + 1 == flight.len()).then_some(current);
+    let shown = match flight.get(*playhead + 1) {
+        Some(next) => lerp_points(&current, next, *alpha),
+        None => current,
+    };
+    let flight_path = use_memo(
+        |flight| {
+            flight
+                .iter()
+                .map(|point| (point.position.x, point.position.y))
+                .collect::<Vec<_>>()
+        },
+        (*flight).clone(),
+    );
+    let target_point = Vector3 {
+        x: state.target.range,
+        y: state.target.height,
+        z: 0.0,
+    };
+    let approach = miss_distance(&flight, target_point);
+    // Once the shot has reached its closest approach the outcome is known;
+    // a miss is only called on landing, when the shot is over.
+    let outcome = approach.and_then(|(point, miss)| {
+        if miss <= state.target.hit_radius && shown.time >= point.time {
+            Some(html! { <div class="hit">{"HIT"}</div> })
+        } else if miss > state.target.hit_radius && landing.is_some() {
+            Some(html! {
+                <div>{format!(
+                    "MISS by {:.1} {}",
+                    units.from_si(Quantity::Length, miss),
+                    units.unit(Quantity::Length),
+                )}</div>
+            })
+        } else {
+            None
+        }
+    });
+    let drift = spin_drift(stability, shown.time, params.twist_direction);
+    let muzzle_rpm = muzzle_spin_rate(params.effective_muzzle_velocity(), params.twist_rate);
+    let sd = sectional_density(params.mass, params.caliber);
+    let position = shown.position;
+    let velocity = shown.velocity;
+    let speed = velocity.magnitude();
+    let mach = speed / params.speed_of_sound_at(position.y);
+
+    // Keeps the density altitude field in step with the raw conditions.
+    {
+        let density_altitude_ref = density_altitude_ref.clone();
+        let density_altitude_typed = density_altitude_typed.clone();
+        use_effect_with_deps(
+            move |&(meters, per_unit)| {
+                if !density_altitude_typed.replace(false) {
+                    if let Some(input) = density_altitude_ref.cast::<HtmlInputElement>() {
+                        input.set_value(&format!("{:.0}", meters / per_unit));
+                    }
+                }
+            },
+            (params.density_altitude(), density_altitude_per_unit),
+        );
+    }
+
+    let labelled = |name: &str, quantity| format!("{} ({})", name, units.unit(quantity));
+    let distance = |value| units.from_si(Quantity::Distance, value);
+    let distance_unit = units.unit(Quantity::Distance);
+    let length_unit = units.unit(Quantity::Length);
+    let tip = |help: Help| html! { <HelpTip label={help.label} body={help.body} /> };
+
+    // Each chart shows load A, and load B on top of it when comparing.
+    type ToPoints<'a> = dyn Fn(&[TrajectoryPoint], &ShotProfile) -> Vec<(f64, f64)> + 'a;
+    let overlay = |color_a, color_b, series: &ToPoints| {
+        let compared = state.load_b.is_some();
+        let mut overlaid = vec![Series {
+            points: series(&trajectory, &state.profile()),
+            color: color_a,
+            label: compared.then_some("Load A"),
+        }];
+        if let (Some(load), Some(points)) = (state.load_b, trajectory_b.as_ref()) {
+            overlaid.push(Series {
+                points: series(points, &load),
+                color: color_b,
+                label: Some("Load B"),
+            });
+        }
+        overlaid
+    };
+    let trajectory_series = overlay("steelblue", "crimson", &|points, load| {
+        points
+            .iter()
+            .map(|point| {
+                let height =
+                    height_in_frame(point, state.frame, load.shot.elevation, load.sight_height);
+                (distance(point.position.x), distance(height))
+            })
+            .collect()
+    });
+    let velocity_series = overlay("darkorange", "purple", &|points, _| {
+        points
+            .iter()
+            .map(|point| {
+                let speed = units.from_si(Quantity::Velocity, point.velocity.magnitude());
+                (distance(point.position.x), speed)
+            })
+            .collect()
+    });
+    let energy_series = overlay("seagreen", "purple", &|points, load| {
+        points
+            .iter()
+            .map(|point| {
+                let energy = kinetic_energy(load.shot.mass, point.velocity.magnitude());
+                (distance(point.position.x), energy)
+            })
+            .collect()
+    });
+    let muzzle_energy = kinetic_energy(params.mass, params.effective_muzzle_velocity());
+    // Zero means no minimum has been set.
+    let energy_floor = (state.target.min_energy > 0.0)
+        .then(|| range_below_energy(&trajectory, settled.params.mass, state.target.min_energy));
+    let energy_markers: Vec<Marker> = energy_floor
+        .flatten()
+        .map(|range| Marker {
+            x: distance(range),
+            label: format!("{:.0} J", state.target.min_energy),
+        })
+        .into_iter()
+        .collect();
+    let joules = |energy: f64| format!("{:.0} J ({:.0} ft·lb)", energy, foot_pounds(energy));
+    let energy = html! {
+        <>
+            <div>{format!(
+                "Energy: {} at the muzzle{}{}",
+                joules(muzzle_energy),
+                point_at_range(&downrange, settled.target_range)
+                    .map(|point| format!(
+                        " / {} at {:.0} {}",
+                        joules(kinetic_energy(settled.params.mass, point.velocity.magnitude())),
+                        distance(settled.target_range),
+                        distance_unit,
+                    ))
+                    .unwrap_or_default(),
+                landing_point(&trajectory)
+                    .map(|point| format!(" / {} at impact", joules(impact(&point, settled.params.mass).energy)))
+                    .unwrap_or_default(),
+            )}</div>
+            { match energy_floor {
+                Some(Some(range)) => html! {
+                    <div class="warning">{format!(
+                        "Energy drops below {} at {:.0} {}",
+                        joules(state.target.min_energy),
+                        distance(range),
+                        distance_unit,
+                    )}</div>
+                },
+                Some(None) if muzzle_energy < state.target.min_energy => html! {
+                    <div class="warning">{format!("Even the muzzle energy is below {}", joules(state.target.min_energy))}</div>
+                },
+                _ => html! {},
+            } }
+        </>
+    };
+    let mach_markers: Vec<Marker> = supersonic_range(&trajectory, &settled.params)
+        .map(|range| Marker {
+            x: distance(range),
+            label: "Mach 1".into(),
+        })
+        .into_iter()
+        .collect();
+
+    let drag_points = use_memo(
+        |model| drag_curve(*model, DRAG_CHART_MAX_MACH, DRAG_CHART_SAMPLES),
+        params.drag_model,
+    );
+    let drag_series = vec![Series {
+        points: (*drag_points).clone(),
+        color: "darkorange",
+        label: None,
+    }];
+    let current_mach = vec![Marker {
+        x: mach,
+        label: format!("Mach {:.2}", mach),
+    }];
+
+    let profile = state.profile();
+
+    // Starts load B, or brings it back in line, as a copy of load A.
+    let on_copy_load_a = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::SetLoadB(Some(profile))))
+    };
+
+    let on_compare_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let Some(input) = input_target(&e) {
+                state.dispatch(ShotAction::SetLoadB(input.checked().then_some(profile)));
+            }
+        })
+    };
+
+    let on_load_b_input = |field: fn(&mut ShotParams, f64, UnitSystem)| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let (Some(value), Some(mut load)) = (parse_input(&e), state.load_b) {
+                field(&mut load.shot, value, units);
+                state.dispatch(ShotAction::SetLoadB(Some(load)));
+            }
+        })
+    };
+
+    let on_load_b_drag_model_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            if let (Some(model), Some(mut load)) = (parse_select(&e), state.load_b) {
+                load.shot.drag_model = model;
+                state.dispatch(ShotAction::SetLoadB(Some(load)));
+            }
+        })
+    };
+
+    let on_export_profile = Callback::from(move |_: MouseEvent| {
+        let json = profile.to_json();
+        if let Err(err) = browser::download("shot-profile.json", "application/json", &json) {
+            web_sys::console::error_1(&err);
+        }
+    });
+
+    let apply_profile = {
+        let state = state.clone();
+        Callback::from(move |profile| state.dispatch(ShotAction::LoadProfile(profile)))
+    };
+
+    {
+        let apply_profile = apply_profile.clone();
+        use_effect_with_deps(
+            move |_| {
+                // Anything unreadable, e.g. from an older version, is ignored
+                // and the form keeps its defaults.
+                if let Some(profile) =
+                    browser::load(STORAGE_KEY).and_then(|json| ShotProfile::from_json(&json).ok())
+                {
+                    apply_profile.emit(profile);
+                }
+            },
+            (),
+        );
+    }
+
+    {
+        let apply_profile = apply_profile.clone();
+        use_effect_with_deps(
+            move |_| {
+                // A shared link takes priority over whatever was remembered.
+                if let Some(query) = browser::query() {
+                    apply_profile.emit(ShotProfile::from_query(&query));
+                }
+            },
+            (),
+        );
+    }
+
+    use_effect_with_deps(
+        |profile| {
+            if *profile == ShotProfile::default() {
+                browser::forget(STORAGE_KEY);
+            } else {
+                browser::store(STORAGE_KEY, &profile.to_json());
+            }
+        },
+        profile,
+    );
+
+    let on_copy_link = {
+        let link_unavailable = link_unavailable.clone();
+        Callback::from(move |_: MouseEvent| {
+            // Rather than share a link that loads a different setup.
+            let fits = profile.fits_in_query();
+            link_unavailable.set(!fits);
+            if !fits {
+                return;
+            }
+            let url = match browser::url_with_query(&profile.to_query()) {
+                Ok(url) => url,
+                Err(err) => return web_sys::console::error_1(&err),
+            };
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(err) = browser::copy_text(&url).await {
+                    web_sys::console::error_1(&err);
+                }
+            });
+        })
+    };
+
+    // Puts the whole live form back how it started; saved presets are kept.
+    let reset = {
+        let state = state.clone();
+        let drag_table_error = drag_table_error.clone();
+        let zero_result = zero_result.clone();
+        let mpbr_result = mpbr_result.clone();
+        let max_range_result = max_range_result.clone();
+        let group_result = group_result.clone();
+        let solve_error = solve_error.clone();
+        let profile_error = profile_error.clone();
+        let location_error = location_error.clone();
+        let table_selected = table_selected.clone();
+        let link_unavailable = link_unavailable.clone();
+        let invalid_field = invalid_field.clone();
+        let preset_name = preset_name.clone();
+        let selected_preset = selected_preset.clone();
+        let flight = flight.clone();
+        let flight_step = flight_step.clone();
+        let playhead = playhead.clone();
+        let alpha = alpha.clone();
+        let computing = computing.clone();
+        let pending_request = pending_request.clone();
+        let clock = clock.clone();
+        Callback::from(move |()| {
+            state.dispatch(ShotAction::Reset);
+            zero_result.set(None);
+            mpbr_result.set(None);
+            max_range_result.set(None);
+            group_result.set(None);
+            solve_error.set(None);
+            profile_error.set(None);
+            location_error.set(false);
+            table_selected.set(false);
+            link_unavailable.set(false);
+            drag_table_error.set(None);
+            invalid_field.set(None);
+            preset_name.set(String::new());
+            selected_preset.set(None);
+
+            // Drop the flight and anything still being computed, and restart
+            // the playback clock so the next submit starts cleanly.
+            flight.set(Vec::new());
+            flight_step.set(TIME_STEP);
+            playhead.set(0);
+            alpha.set(0.0);
+            computing.set(false);
+            *pending_request.borrow_mut() = None;
+            let mut clock = clock.borrow_mut();
+            clock.0 = None;
+            clock.1.reset();
+        })
+    };
+
+    let on_reset = reset.reform(|_: MouseEvent| ());
+
+    // Enter fires, Space pauses and R resets from anywhere but a form field
+    // or button, which already handle those keys themselves.
+    {
+        let fire = fire.clone();
+        let toggle_pause = toggle_pause.clone();
+        let reset = reset.clone();
+        use_event_with_window("keydown", move |e: KeyboardEvent| {
+            if e.ctrl_key() || e.meta_key() || e.alt_key() {
+                return;
+            }
+            let in_field = e
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                .is_some_and(|element| {
+                    matches!(
+                        element.tag_name().as_str(),
+                        "INPUT" | "TEXTAREA" | "SELECT" | "BUTTON"
+                    )
+                });
+            let Some(action) = shortcut(&e.key(), in_field) else {
+                return;
+            };
+            e.prevent_default();
+            match action {
+                Shortcut::Fire => fire.emit(()),
+                Shortcut::TogglePause => toggle_pause.emit(()),
+                Shortcut::Reset => reset.emit(()),
+            }
+        });
+    }
+
+    // Saved presets are written back whenever they change.
+    let update_presets = {
+        let presets = presets.clone();
+        Callback::from(move |updated: Presets| {
+            browser::store(PRESETS_KEY, &updated.to_json());
+            presets.set(updated);
+        })
+    };
+
+    let on_preset_name_input = {
+        let preset_name = preset_name.clone();
+        Callback::from(move |e: InputEvent| {
+            preset_name.set(parse_input::<String>(&e).unwrap_or_default());
+        })
+    };
+
+    let on_save_preset = {
+        let presets = presets.clone();
+        let preset_name = preset_name.clone();
+        let selected_preset = selected_preset.clone();
+        let update_presets = update_presets.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut updated = (*presets).clone();
+            updated.save(&preset_name, profile);
+            update_presets.emit(updated);
+            selected_preset.set(Some((*preset_name).clone()));
+        })
+    };
+
+    let on_undo = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::Undo))
+    };
+
+    let on_redo = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::Redo))
+    };
+
+    let on_preset_change = {
+        let presets = presets.clone();
+        let selected_preset = selected_preset.clone();
+        let apply_profile = apply_profile.clone();
+        Callback::from(move |e: Event| {
+            let name = parse_select::<String>(&e).filter(|name| !name.is_empty());
+            if let Some(profile) = name.as_deref().and_then(|name| presets.get(name)) {
+                apply_profile.emit(profile);
+            }
+            selected_preset.set(name);
+        })
+    };
+
+    let on_delete_preset = {
+        let presets = presets.clone();
+        let selected_preset = selected_preset.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(name) = (*selected_preset).clone() else {
+                return;
+            };
+            let mut updated = (*presets).clone();
+            updated.remove(&name);
+            update_presets.emit(updated);
+            selected_preset.set(None);
+        })
+    };
+
+    let on_cartridge_change = {
+        let apply_profile = apply_profile.clone();
+        Callback::from(move |e: Event| {
+            let Some(preset) = parse_select::<String>(&e).and_then(|name| cartridge::find(&name))
+            else {
+                return;
+            };
+            let shot = ShotParams {
+                caliber: preset.caliber,
+                mass: preset.mass,
+                ballistic_coefficient: preset.ballistic_coefficient,
+                // Bands measured for another bullet would override the new BC.
+                bc_bands: BcBands::default(),
+                drag_model: preset.drag_model,
+                muzzle_velocity: preset.muzzle_velocity,
+                ..params
+            };
+            apply_profile.emit(ShotProfile { shot, ..profile });
+        })
+    };
+
+    let on_import_profile = {
+        let profile_error = profile_error.clone();
+        Callback::from(move |e: Event| {
+            let Some(input) = input_target(&e) else {
+                return;
+            };
+            let apply_profile = apply_profile.clone();
+            let profile_error = profile_error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let text = browser::read_text(&input).await;
+                // Let the same file be picked again after fixing it.
+                input.set_value("");
+                match text {
+                    Ok(text) => match ShotProfile::from_json(&text) {
+                        Ok(profile) => {
+                            apply_profile.emit(profile);
+                            profile_error.set(None);
+                        }
+                        Err(err) => profile_error.set(Some(err.to_string())),
+                    },
+                    Err(_) => profile_error.set(Some("could not read the selected file".into())),
+                }
+            });
+        })
+    };
+
+    let range_rows = in_frame(
+        &tables.0,
+        state.frame,
+        settled.params.elevation,
+        settled.sight_height,
+    );
+
+    let on_download_csv = {
+        let range_rows = range_rows.clone();
+        Callback::from(move |_: MouseEvent| {
+            let csv = to_csv(&range_rows, units);
+            if let Err(err) = browser::download("range-table.csv", "text/csv", &csv) {
+                web_sys::console::error_1(&err);
+            }
+        })
+    };
+
+    let on_copy_table = {
+        let range_rows = range_rows.clone();
+        let range_table_ref = range_table_ref.clone();
+        let table_selected = table_selected.clone();
+        Callback::from(move |_: MouseEvent| {
+            let tsv = to_tsv(&range_rows, units);
+            let range_table_ref = range_table_ref.clone();
+            let table_selected = table_selected.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match browser::copy_text(&tsv).await {
+                    Ok(()) => table_selected.set(false),
+                    Err(err) => {
+                        web_sys::console::error_1(&err);
+                        let selected = range_table_ref
+                            .cast::<web_sys::Element>()
+                            .map(|table| browser::select_contents(&table).is_ok());
+                        table_selected.set(selected.unwrap_or(false));
+                    }
+                }
+            });
+        })
+    };
+
+    let flight = match (landing_point(&trajectory), max_ordinate(&trajectory)) {
+        (Some(landing), Some(apex)) => html! {
+            <>
+                <div>{format!(
+                    "Time of flight: {:.3} s / max ordinate: {:.2} {} at {:.1} {}",
+                    landing.time,
+                    distance(apex.position.y),
+                    distance_unit,
+                    distance(apex.position.x),
+                    distance_unit,
+                )}</div>
+                if let Some(cost) = range_cost.filter(|cost| distance(cost.abs()) >= 0.5) {
+                    <div>{format!(
+                        "{} {:.0} {} of range",
+                        if cost > 0.0 { "The headwind costs" } else { "The tailwind adds" },
+                        distance(cost.abs()),
+                        distance_unit,
+                    )}</div>
+                }
+            </>
+        },
+        _ => html! {},
+    };
+
+    // The holds are read off the settled flight, so they're worked out from
+    // the same inputs it was flown with.
+    let holds = {
+        let params = settled.params;
+        let stability = params.stability();
+        match point_at_range(&downrange, settled.target_range) {
+            Some(point) => {
+                let flat_drop = -height_above_sight_line(&point, settled.sight_height);
+                let drop = inclined_drop(flat_drop, state.target.look_angle);
+                let windage =
+                    point.position.z + spin_drift(stability, point.time, params.twist_direction);
+                let jump = aerodynamic_jump(
+                    stability,
+                    params.caliber,
+                    params.bullet_length,
+                    crosswind(params.wind, params.wind_direction),
+                    params.twist_direction,
+                ) * point.position.x;
+                let elevation_hold = correction(drop, point.position.x);
+                let jump_hold = correction(jump, point.position.x);
+                let windage_hold = correction(windage, point.position.x);
+                let lead = lead(state.target.speed, state.target.angle, point.time);
+                let lead_hold = correction(lead, point.position.x);
+                let dial = |mil: f64, positive: &'static str, negative: &'static str| {
+                    clicks(mil, AngleUnit::Mil, state.click_value, state.click_unit).map(|dial| {
+                        let direction = if dial.count < 0 { negative } else { positive };
+                        format!(
+                            "{} clicks {} ({:+.2} {} left over)",
+                            dial.count.abs(),
+                            direction,
+                            dial.residual,
+                            state.click_unit.label(),
+                        )
+                    })
+                };
+                // Drop is dialled up to raise the impact; drift right is dialled left.
+                // Jump already lifts the shot, so it comes off what is dialled up.
+                let elevation_clicks = dial(elevation_hold.mil - jump_hold.mil, "up", "down");
+                let windage_clicks = dial(windage_hold.mil, "left", "right");
+                html! {
+                    <div>
+                        <div>{format!(
+                            "Drop at {:.0} {} ({}): {:.1} {} ({:.2} MOA / {:.2} MIL)",
+                            distance(settled.target_range),
+                            distance_unit,
+                            DROP_SIGN,
+                            units.from_si(Quantity::Length, -drop),
+                            length_unit,
+                            elevation_hold.moa,
+                            elevation_hold.mil,
+                        )}</div>
+                        if state.target.look_angle != 0.0 {
+                            <div>{format!(
+                                "Flat-ground drop ({}): {:.1} {}",
+                                DROP_SIGN,
+                                units.from_si(Quantity::Length, -flat_drop),
+                                length_unit,
+                            )}</div>
+                        }
+                        if jump != 0.0 {
+                            <div>{format!(
+                                "Aerodynamic jump: {:+.1} {} ({:+.2} MOA / {:+.2} MIL)",
+                                units.from_si(Quantity::Length, jump),
+                                length_unit,
+                                jump_hold.moa,
+                                jump_hold.mil,
+                            )}</div>
+                        }
+                        <div>{format!(
+                            "Windage: {:.1} {} ({:.2} MOA / {:.2} MIL)",
+                            units.from_si(Quantity::Length, windage),
+                            length_unit,
+                            windage_hold.moa,
+                            windage_hold.mil,
+                        )}</div>
+                        if let (Some(elevation_clicks), Some(windage_clicks)) = (elevation_clicks, windage_clicks) {
+                            <div>{format!("Dial: {}, {}", elevation_clicks, windage_clicks)}</div>
+                        }
+                        if let Some(space) = danger_space(&trajectory, settled.target_range, state.target.size, settled.sight_height) {
+                            <div>{format!(
+                                "Danger space for a {:.1} {} target: {:.0}–{:.0} {} ({:.0} {})",
+                                units.from_si(Quantity::Length, state.target.size),
+                                length_unit,
+                                distance(space.start),
+                                distance(space.end),
+                                distance_unit,
+                                distance(space.length()),
+                                distance_unit,
+                            )}</div>
+                        }
+                        if state.target.speed > 0.0 {
+                            <div>{format!(
+                                "Lead: {:.2} {} ({:.2} MOA / {:.2} MIL)",
+                                distance(lead),
+                                distance_unit,
+                                lead_hold.moa,
+                                lead_hold.mil,
+                            )}</div>
+                        }
+                    </div>
+                }
+            }
+            None => html! { <div>{"Target is out of range"}</div> },
+        }
+    };
+
+    if state.print_view {
+        let on_print = Callback::from(|_: MouseEvent| {
+            if let Err(err) = browser::print() {
+                web_sys::console::error_1(&err);
+            }
+        });
+        let on_close = {
+            let state = state.clone();
+            Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::SetPrintView(false)))
+        };
+        return html! {
+            <div>
+                <div class="no-print">
+                    <button type="button" onclick={on_print}>{"Print"}</button>
+                    <button type="button" onclick={on_close}>{"Back to the calculator"}</button>
+                </div>
+                <RangeCard
+                    rows={range_card(&tables.0, state.click_value, state.click_unit)}
+                    units={units}
+                    click_value={state.click_value}
+                    click_unit={state.click_unit}
+                />
+            </div>
+        };
+    }
+
+    let on_print_view = {
+        let state = state.clone();
+        Callback::from(move |_: MouseEvent| state.dispatch(ShotAction::SetPrintView(true)))
+    };
+
+    html! {
+        <div>
+            <button type="button" onclick={on_unit_toggle}>{format!("Units: {}", units.label())}</button>
+            <button type="button" onclick={on_frame_toggle}>{format!("Heights from: {}", state.frame.label())}</button>
+            <button type="button" onclick={on_export_profile}>{"Export JSON"}</button>
+            <button type="button" onclick={on_reset}>{"Reset to defaults"}</button>
+            <div>{format!(
+                "Keys: {}",
+                BINDINGS
+                    .iter()
+                    .map(|binding| format!("{} {}", binding.label, binding.description))
+                    .collect::<Vec<_>>()
+                    .join(" · "),
+            )}</div>
+            <button type="button" onclick={on_undo} disabled={!state.can_undo()}>{"Undo"}</button>
+            <button type="button" onclick={on_redo} disabled={!state.history.can_redo()}>{"Redo"}</button>
+            <button type="button" onclick={on_copy_link}>{"Copy shareable link"}</button>
+            if *link_unavailable {
+                <div class="warning">{"This setup can't be written into a link; export the profile to share it instead."}</div>
+            }
+            <div>
+                <input type="text" placeholder="Preset name" value={(*preset_name).clone()} oninput={on_preset_name_input} />
+                <button type="button" onclick={on_save_preset} disabled={preset_name.is_empty()}>{"Save"}</button>
+                <select onchange={on_preset_change}>
+                    <option value="" selected={selected_preset.is_none()}>{"Load preset…"}</option>
+                    { for presets.names().into_iter().map(|name| html! {
+                        <option value={name.to_string()} selected={selected_preset.as_deref() == Some(name)}>{name}</option>
+                    }) }
+                </select>
+                <button type="button" onclick={on_delete_preset} disabled={selected_preset.is_none()}>{"Delete preset"}</button>
+            </div>
+            <label>
+                {"Import JSON "}
+                <input type="file" accept="application/json,.json" onchange={on_import_profile} />
+            </label>
+            <select onchange={on_cartridge_change}>
+                <option value="" selected=true>{"Factory cartridge…"}</option>
+                { for CARTRIDGES.iter().map(|preset| html! {
+                    <option value={preset.name}>{preset.name}</option>
+                }) }
+            </select>
+            if !errors.is_empty() {
+                <ul>
+                    { for errors.iter().map(|error| html! { <li>{error.to_string()}</li> }) }
+                </ul>
+            }
+            if let Some(field) = (*invalid_field).clone() {
+                <div>{format!("{} is not a valid number", field)}</div>
+            }
+            if let Some(error) = (*profile_error).clone() {
+                <div>{format!("Import failed: {}", error)}</div>
+            }
+            // Inputs are uncontrolled, so remount them when the units change
+            // or a profile is loaded rather than leaving stale numbers on screen.
+            <form key={format!("{}-{}", units.label(), state.revision)} oninput={on_form_input} onsubmit={on_submit}>
+                <input type="number" step="0.01" placeholder={labelled("Wind", Quantity::Velocity)} oninput={on_wind_input} />
+                {tip(help::WIND)}
+                <input type="number" step="1" min="0" max="360" placeholder="Wind Direction (° from, 0 = head)" oninput={on_wind_direction_input} />
+                {tip(help::WIND_DIRECTION)}
+                { for (0..state.wind_samples.len()).map(|index| html! {
+                    <div key={index}>
+                        <input type="number" step="any" placeholder={labelled("Wind height", Quantity::Distance)}
+                            oninput={on_wind_sample_input(index, |sample, value, units| sample.height = units.to_si(Quantity::Distance, value))} />
+                        <input type="number" step="0.1" placeholder={labelled("Wind speed", Quantity::Velocity)}
+                            oninput={on_wind_sample_input(index, |sample, value, units| sample.speed = units.to_si(Quantity::Velocity, value))} />
+                        <input type="number" step="1" min="0" max="360" placeholder="Wind from (°)"
+                            oninput={on_wind_sample_input(index, |sample, value, _| sample.direction = value)} />
+                    </div>
+                }) }
+                if state.wind_samples.len() < MAX_WIND_SAMPLES {
+                    <button type="button" onclick={on_add_wind_sample}>{"Add wind sample"}</button>
+                }
+                if !state.wind_samples.is_empty() {
+                    <button type="button" onclick={on_remove_wind_sample}>{"Remove wind sample"}</button>
+                }
+                <input type="number" step="any" placeholder={format!("Elevation ({})", state.elevation_unit.label())} ref={elevation_ref} oninput={on_elevation_input} />
+                {tip(help::ELEVATION)}
+                <select onchange={on_elevation_unit_change}>
+                    { for AngleUnit::ALL.iter().map(|unit| html! {
+                        <option value={unit.label()} selected={*unit == state.elevation_unit}>{unit.label()}</option>
+                    }) }
+                </select>
+                <div>{format!(
+                    "= {:.4}° / {:.2} MIL / {:.2} MOA",
+                    params.elevation,
+                    AngleUnit::Mil.from_degrees(params.elevation),
+                    AngleUnit::Moa.from_degrees(params.elevation),
+                )}</div>
+                <input type="number" step="any" placeholder={labelled("Caliber", Quantity::Length)} oninput={on_caliber_input} />
+                {tip(help::CALIBER)}
+                <input type="number" placeholder="Ballistic Coefficient" ref={ballistic_coefficient_ref} oninput={on_ballistic_coefficient_input} step="0.01" min="0" max="1" />
+                {tip(help::BALLISTIC_COEFFICIENT)}
+                { for (0..state.bc_bands.len()).map(|index| html! {
+                    <div key={index}>
+                        <input type="number" step="1" min="0" placeholder={labelled("BC band from", Quantity::Velocity)}
+                            oninput={on_bc_band_input(index, |band, value, units| if value >= 0.0 { band.velocity = units.to_si(Quantity::Velocity, value) })} />
+                        <input type="number" step="0.001" min="0" max="1" placeholder="Band BC"
+                            oninput={on_bc_band_input(index, |band, value, _| if value > 0.0 { band.ballistic_coefficient = value })} />
+                    </div>
+                }) }
+                if state.bc_bands.len() < MAX_BC_BANDS {
+                    <button type="button" onclick={on_add_bc_band}>{"Add BC band"}</button>
+                }
+                if !state.bc_bands.is_empty() {
+                    <button type="button" onclick={on_remove_bc_band}>{"Remove BC band"}</button>
+                }
+                <input type="number" step="1" min="0" placeholder={labelled("Muzzle Velocity", Quantity::Velocity)} oninput={on_muzzle_velocity_input} />
+                {tip(help::MUZZLE_VELOCITY)}
+                <select onchange={on_drag_model_change}>
+                    { for DragModel::ALL.iter().map(|model| html! {
+                        <option value={model.label()} selected={*model == params.drag_model}>{model.label()}</option>
+                    }) }
+                    if state.custom_drag.is_some() {
+                        <option value="Custom" selected={matches!(params.drag_model, DragModel::Custom(_))}>{"Custom"}</option>
+                    }
+                </select>
+                {tip(help::DRAG_MODEL)}
+                <div>
+                    <input type="number" step="0.001" min="0" placeholder="BC to convert" oninput={on_converter_bc_input} />
+                    <select onchange={on_converter_model_change}>
+                        { for [DragModel::G1, DragModel::G7].iter().map(|model| html! {
+                            <option value={model.label()} selected={*model == state.converter_model}>{model.label()}</option>
+                        }) }
+                    </select>
+                    if let Some(bc) = converted_bc {
+                        {format!(
+                            " = {:.3} {} at the muzzle (form factor {:.3})",
+                            bc,
+                            converted_model.label(),
+                            form_factor(sd, bc),
+                        )}
+                        <button type="button" onclick={on_use_converted_bc}>{"Use as BC"}</button>
+                    }
+                    {tip(help::BC_CONVERTER)}
+                </div>
+                <textarea
+                    placeholder={format!("Custom drag table: one mach,cd pair per line, up to {} points", MAX_DRAG_POINTS)}
+                    onchange={on_drag_table_paste}
+                />
+                <input type="file" accept="text/csv,.csv" onchange={on_drag_table_upload} />
+                {tip(help::DRAG_TABLE)}
+                if let Some(error) = (*drag_table_error).clone() {
+                    <div>{format!("Drag table not used: {}", error)}</div>
+                }
+                <input type="number" step="0.1" min="-90" max="90" placeholder="Latitude" ref={latitude_ref} oninput={on_latitude_input} />
+                {tip(help::LATITUDE)}
+                <button type="button" onclick={on_use_location}>{"Use my location"}</button>
+                if *location_error {
+                    <div>{"Location unavailable: enter the latitude by hand"}</div>
+                }
+                <input type="number" step="1" min="0" max="360" placeholder="Azimuth" oninput={on_azimuth_input} />
+                {tip(help::AZIMUTH)}
+                <input type="number" step="0.1" min="0" placeholder="Twist Rate (in/turn)" oninput={on_twist_rate_input} />
+                {tip(help::TWIST_RATE)}
+                <select onchange={on_twist_direction_change}>
+                    { for TwistDirection::ALL.iter().map(|direction| html! {
+                        <option value={direction.label()} selected={*direction == params.twist_direction}>{direction.label()}</option>
+                    }) }
+                </select>
+                <input type="number" step="any" min="0" placeholder={labelled("Bullet Length", Quantity::Length)} oninput={on_bullet_length_input} />
+                {tip(help::BULLET_LENGTH)}
+                <input type="number" step="0.1" min="0" placeholder={labelled("Mass", Quantity::Mass)} oninput={on_mass_input} />
+                {tip(help::MASS)}
+                if units == UnitSystem::Imperial {
+                    <div>{format!(
+                        "= {:.2} g",
+                        UnitSystem::Metric.from_si(Quantity::Mass, params.mass),
+                    )}</div>
+                }
+                <input type="number" step="1" placeholder={labelled("Altitude", Quantity::Distance)} ref={altitude_ref} oninput={on_altitude_input} />
+                {tip(help::ALTITUDE)}
+                <input type="number" step="0.1" placeholder="Temperature (°C)" ref={temperature_ref} oninput={on_temperature_input} />
+                {tip(help::TEMPERATURE)}
+                <input type="number" step="0.01" min="0" placeholder="Pressure (hPa)" ref={pressure_ref} oninput={on_pressure_input} />
+                {tip(help::PRESSURE)}
+                <input type="number" step="1" min="0" max="100" placeholder="Humidity (%)" ref={humidity_ref} oninput={on_humidity_input} />
+                {tip(help::HUMIDITY)}
+                <button type="button" onclick={on_standard_conditions}>{"Standard conditions"}</button>
+                <input type="number" step="1" placeholder={format!("Density Altitude ({})", if units == UnitSystem::Imperial { "ft" } else { "m" })} ref={density_altitude_ref} oninput={on_density_altitude_input} />
+                {tip(help::DENSITY_ALTITUDE)}
+                <input type="number" step="any" placeholder={format!("Velocity Temp Sensitivity ({}/°C)", units.unit(Quantity::Velocity))} oninput={on_velocity_temp_sensitivity_input} />
+                {tip(help::VELOCITY_TEMP_SENSITIVITY)}
+                <input type="number" step="0.1" placeholder="Reference Temperature (°C)" oninput={on_reference_temperature_input} />
+                {tip(help::REFERENCE_TEMPERATURE)}
+                if params.velocity_temp_sensitivity != 0.0 {
+                    <div>{format!(
+                        "Corrected muzzle velocity: {:.0} {}",
+                        units.from_si(Quantity::Velocity, params.effective_muzzle_velocity()),
+                        units.unit(Quantity::Velocity),
+                    )}</div>
+                }
+                <select onchange={on_integrator_change}>
+                    { for Integrator::ALL.iter().map(|method| html! {
+                        <option value={method.label()} selected={*method == params.integrator}>{method.label()}</option>
+                    }) }
+                </select>
+                {tip(help::INTEGRATOR)}
+                <label>
+                    <input type="checkbox" checked={params.drag_enabled} onchange={on_drag_enabled_change} />
+                    {"Air resistance"}
+                </label>
+                <label>
+                    <input type="checkbox" checked={params.magnus_enabled} onchange={on_magnus_enabled_change} />
+                    {"Magnus effect"}
+                </label>
+                <label>
+                    <input type="checkbox" checked={params.eotvos_enabled} onchange={on_eotvos_enabled_change} />
+                    {"Eötvös effect"}
+                </label>
+                <input type="number" step="0.0005" min={MIN_TIME_STEP.to_string()} max={MAX_TIME_STEP.to_string()} placeholder="Time Step (s)" oninput={on_time_step_input} />
+                {tip(help::TIME_STEP)}
+                if drag_loss_per_step(&params) > MAX_DRAG_LOSS_PER_STEP {
+                    <div class="warning">{format!(
+                        "A {} s step is too coarse for this much drag; the trajectory will be off",
+                        params.time_step,
+                    )}</div>
+                }
+                <input type="number" step="1" min="0" placeholder="Max Flight Time (s)" oninput={on_max_flight_time_input} />
+                {tip(help::MAX_FLIGHT_TIME)}
+                <input type="number" step="100" min="0" placeholder={labelled("Max Range", Quantity::Distance)} oninput={on_max_range_input} />
+                {tip(help::MAX_RANGE)}
+                if truncated(&trajectory) {
+                    <div class="warning">{format!(
+                        "Simulation truncated after {:.1} s and {:.0} {}, before the shot came down",
+                        trajectory.last().map_or(0.0, |point| point.time),
+                        distance(trajectory.last().map_or(0.0, |point| point.position.x)),
+                        distance_unit,
+                    )}</div>
+                }
+                <input type="number" step="1" min="0" placeholder={labelled("Target Range", Quantity::Distance)} oninput={on_target_range_input} />
+                <input type="number" step="any" placeholder={labelled("Target Height", Quantity::Distance)} oninput={on_target_height_input} />
+                <input type="number" step="any" min="0" placeholder={labelled("Hit Radius", Quantity::Length)} oninput={on_hit_radius_input} />
+                <input type="number" step="any" min="0" placeholder={labelled("Target Size", Quantity::Length)} oninput={on_target_size_input} />
+                <input type="number" step="1" min="0" placeholder="Minimum Energy (J)" oninput={on_min_energy_input} />
+                <button type="button" onclick={on_solve}>{"Solve"}</button>
+                <input type="number" step="0.1" min="0" placeholder={labelled("Target Speed", Quantity::Velocity)} oninput={on_target_speed_input} />
+                <input type="number" step="1" min="0" max="360" placeholder="Target Angle (°, 90 = crossing)" oninput={on_target_angle_input} />
+                <input type="number" step="1" min="-90" max="90" placeholder="Look Angle (°, + = uphill)" oninput={on_look_angle_input} />
+                {tip(help::LOOK_ANGLE)}
+                <input type="number" step="any" min="0" placeholder="Click Value" oninput={on_click_value_input} />
+                {tip(help::CLICK_VALUE)}
+                <select onchange={on_click_unit_change}>
+                    { for [AngleUnit::Moa, AngleUnit::Mil].iter().map(|unit| html! {
+                        <option value={unit.label()} selected={*unit == state.click_unit}>{unit.label()}</option>
+                    }) }
+                </select>
+                <input type="number" step="1" min="0" placeholder={labelled("Zero Distance", Quantity::Distance)} oninput={on_zero_distance_input} />
+                {tip(help::ZERO_DISTANCE)}
+                <input type="number" step="any" min="0" placeholder={labelled("Sight Height", Quantity::Length)} oninput={on_sight_height_input} />
+                {tip(help::SIGHT_HEIGHT)}
+                <button type="button" onclick={on_zero}>{"Zero"}</button>
+                <input type="number" step="any" min="0" placeholder={labelled("Vital Zone", Quantity::Length)} oninput={on_vital_zone_diameter_input} />
+                <button type="button" onclick={on_mpbr}>{"MPBR"}</button>
+                <button type="button" onclick={on_max_range}>{"Max range"}</button>
+                <input type="number" step="1" min="1" placeholder="Group Shots" oninput={on_group_shots_input} />
+                <input type="number" step="any" min="0" placeholder={labelled("Velocity SD", Quantity::Velocity)} oninput={on_velocity_sd_input} />
+                <input type="number" step="any" placeholder={labelled("Wind Min", Quantity::Velocity)} oninput={on_gust_min_input} />
+                <input type="number" step="any" placeholder={labelled("Wind Max", Quantity::Velocity)} oninput={on_gust_max_input} />
+                <input type="number" step="1" min="0" placeholder="Seed" oninput={on_group_seed_input} />
+                <button type="button" onclick={on_simulate_group}>{"Simulate group"}</button>
+                <input type="number" step="1" min="0" placeholder={labelled("Table Step", Quantity::Distance)} oninput={on_table_step_input} />
+                <input type="number" step="1" min="0" placeholder={labelled("Table Max", Quantity::Distance)} oninput={on_table_max_input} />
+                <input type="number" step="any" min="0" placeholder={labelled("Reference Wind", Quantity::Velocity)} oninput={on_reference_wind_input} />
+                <input type="number" step="1" min="0" placeholder={labelled("Bracket Range", Quantity::Distance)} oninput={on_bracket_center_input} />
+                <input type="number" step="1" min="0" placeholder={labelled("Bracket Step", Quantity::Distance)} oninput={on_bracket_step_input} />
+                <button type="submit" disabled={!errors.is_empty() || invalid_field.is_some()}>{"Submit"}</button>
+            </form>
+            <label>
+                <input type="checkbox" checked={state.load_b.is_some()} onchange={on_compare_change} />
+                {"Compare with a second load"}
+            </label>
+            if let Some(load) = state.load_b {
+                <details open=true key={units.label()}>
+                    <summary>{"Load B"}</summary>
+                    <button type="button" onclick={on_copy_load_a}>{"Copy load A"}</button>
+                    <input type="number" step="any" placeholder="Elevation (°)"
+                        oninput={on_load_b_input(|shot, value, _| shot.elevation = value)} />
+                    <input type="number" step="any" placeholder={labelled("Caliber", Quantity::Length)}
+                        oninput={on_load_b_input(|shot, value, units| shot.caliber = units.to_si(Quantity::Length, value))} />
+                    <input type="number" step="0.01" min="0" max="1" placeholder="Ballistic Coefficient"
+                        oninput={on_load_b_input(|shot, value, _| shot.ballistic_coefficient = value)} />
+                    <input type="number" step="1" min="0" placeholder={labelled("Muzzle Velocity", Quantity::Velocity)}
+                        oninput={on_load_b_input(|shot, value, units| shot.muzzle_velocity = units.to_si(Quantity::Velocity, value))} />
+                    <select onchange={on_load_b_drag_model_change}>
+                        { for DragModel::ALL.iter().map(|model| html! {
+                            <option value={model.label()} selected={*model == load.shot.drag_model}>{model.label()}</option>
+                        }) }
+                    </select>
+                    <input type="number" step="any" min="0" placeholder={labelled("Bullet Length", Quantity::Length)}
+                        oninput={on_load_b_input(|shot, value, units| shot.bullet_length = units.to_si(Quantity::Length, value))} />
+                    <input type="number" step="0.1" min="0" placeholder={labelled("Mass", Quantity::Mass)}
+                        oninput={on_load_b_input(|shot, value, units| shot.mass = units.to_si(Quantity::Mass, value))} />
+                    <input type="number" step="0.1" min="0" placeholder="Twist Rate (in/turn)"
+                        oninput={on_load_b_input(|shot, value, _| shot.twist_rate = value)} />
+                </details>
+            }
+            <button type="button" onclick={on_pause_toggle}>{if state.running { "Pause" } else { "Resume" }}</button>
+            if *computing {
+                <div class="spinner">{"Computing trajectory…"}</div>
+            }
+            <button type="button" onclick={on_step} disabled={state.running}>{"Step"}</button>
+            <div>{format!("Tick: {}", *playhead)}</div>
+            <FlightCanvas
+                path={flight_path}
+                flown={*playhead + 1}
+                head={(shown.position.x, shown.position.y)}
+                target={Some(((target_point.x, target_point.y), state.target.hit_radius))}
+            />
+            { for outcome }
+            <label>
+                {format!("Playback speed: {}x ", state.time_scale)}
+                <input type="range" min="0.25" max="10" step="0.25" value={state.time_scale.to_string()} oninput={on_time_scale_input} />
+            </label>
+            if position.x.is_finite() && position.y.is_finite() {
+                <div>{format!(
+                    "Position: ({:.2}, {:.2}) {}",
+                    distance(position.x),
+                    distance(height_in_frame(&shown, state.frame, params.elevation, state.sight_height)),
+                    distance_unit,
+                )}</div>
+            } else {
+                <div>{"Position unavailable: check the inputs above"}</div>
+            }
+            <div>{format!(
+                "Speed: {:.0} {} / Mach: {:.2} / time: {:.3} s",
+                units.from_si(Quantity::Velocity, speed),
+                units.unit(Quantity::Velocity),
+                mach,
+                shown.time,
+            )}</div>
+            <div>{format!("Lateral deflection: {:.3} {}", distance(position.z), distance_unit)}</div>
+            <div>{format!(
+                "Sectional density: {:.3} lb/in² / form factor: {:.3}",
+                sd,
+                form_factor(sd, params.ballistic_coefficient),
+            )}</div>
+            <div>{format!("Spin drift: {:.3} {}", distance(drift), distance_unit)}</div>
+            { if stability < UNSTABLE_STABILITY {
+                html! { <div class="warning" style="color: red">{format!("Stability (Sg): {:.2} — unstable, the bullet will tumble", stability)}</div> }
+            } else if stability < MARGINAL_STABILITY {
+                html! { <div class="warning" style="color: red">{format!("Stability (Sg): {:.2} — marginal", stability)}</div> }
+            } else {
+                html! { <div>{format!("Stability (Sg): {:.2}", stability)}</div> }
+            } }
+            <div>{format!(
+                "Spin: {:.0} rpm at the muzzle / {:.0} rpm now",
+                muzzle_rpm,
+                spin_rate(muzzle_rpm, shown.time),
+            )}</div>
+            if let Some(point) = landing {
+                <div>{format!("Impact at {:.1} {}", distance(point.position.x), distance_unit)}</div>
+                <div>{{
+                    let terminal = impact(&point, params.mass);
+                    format!(
+                        "Impact velocity: {:.0} {} / energy: {} / angle of fall: {:.2}°",
+                        units.from_si(Quantity::Velocity, terminal.velocity),
+                        units.unit(Quantity::Velocity),
+                        joules(terminal.energy),
+                        terminal.angle_of_fall,
+                    )
+                }}</div>
+            }
+            if let Some(error) = *solve_error {
+                <div>{error.to_string()}</div>
+            }
+            { match *zero_result {
+                Some(Some(result)) => html! {
+                    <div>{format!(
+                        "Near zero: {:.1} {} / far zero: {}",
+                        distance(result.near_zero),
+                        distance_unit,
+                        match result.far_zero {
+                            Some(far_zero) => format!("{:.1} {}", distance(far_zero), distance_unit),
+                            None => "never crosses back".to_string(),
+                        },
+                    )}</div>
+                },
+                Some(None) => html! { <div>{"Unable to zero at that distance"}</div> },
+                None => html! {},
+            } }
+            { match *mpbr_result {
+                Some(Some(result)) => html! {
+                    <div>{format!(
+                        "Zero at {:.0} {} for a point-blank range of {:.0} {}",
+                        distance(result.zero_distance),
+                        distance_unit,
+                        distance(result.max_range),
+                        distance_unit,
+                    )}</div>
+                },
+                Some(None) => html! { <div>{"No point-blank zero fits that vital zone"}</div> },
+                None => html! {},
+            } }
+            { match *max_range_result {
+                Some(Ok(best)) => html! {
+                    <div>{format!(
+                        "Maximum range {:.0} {} at {:.2}° elevation",
+                        distance(best.range),
+                        distance_unit,
+                        best.elevation,
+                    )}</div>
+                },
+                Some(Err(error)) => html! { <div>{error.to_string()}</div> },
+                None => html! {},
+            } }
+            if let Some(range) = transonic_range(&downrange, temperature_at(settled.params.altitude, settled.params.temperature)) {
+                <div class="warning">{format!(
+                    "Warning: the bullet goes transonic at {:.0} {}, short of the target",
+                    distance(range),
+                    distance_unit,
+                )}</div>
+            }
+            if let Some(group) = (*group_result).clone() {
+                <div>{format!(
+                    "Mean point of impact: {:.1} {} right, {:.1} {} up / extreme spread {:.1} {} ({} shots)",
+                    units.from_si(Quantity::Length, group.mean.0),
+                    length_unit,
+                    units.from_si(Quantity::Length, group.mean.1),
+                    length_unit,
+                    units.from_si(Quantity::Length, group.extreme_spread),
+                    length_unit,
+                    group.impacts.len(),
+                )}</div>
+                <LineChart
+                    series={vec![Series {
+                        points: group
+                            .impacts
+                            .iter()
+                            .map(|&(right, up)| (units.from_si(Quantity::Length, right), units.from_si(Quantity::Length, up)))
+                            .collect(),
+                        color: "black",
+                        label: None,
+                    }]}
+                    x_label={labelled("Windage", Quantity::Length)}
+                    y_label={labelled("Height", Quantity::Length)}
+                    scatter=true
+                />
+            }
+            if settling {
+                <div class="spinner">{"Updating tables and charts…"}</div>
+            }
+            <div><strong>{match *supersonic {
+                Some(range) => format!("Supersonic to {:.0} {}", distance(range), distance_unit),
+                None if settled.params.effective_muzzle_velocity() < settled.params.speed_of_sound_at(0.0) => {
+                    "Subsonic from the muzzle".to_string()
+                }
+                None => format!(
+                    "Supersonic past {:.0} {}",
+                    distance(SUPERSONIC_SEARCH_RANGE),
+                    distance_unit,
+                ),
+            }}</strong></div>
+            if let Some(lift) = (*eotvos).filter(|_| settled.params.drag_enabled) {
+                <div>{format!(
+                    "Eötvös effect at {:.0} {}: {:+.1} {}{}",
+                    distance(settled.target_range),
+                    distance_unit,
+                    units.from_si(Quantity::Length, lift),
+                    length_unit,
+                    if settled.params.eotvos_enabled { "" } else { " (not applied)" },
+                )}</div>
+            }
+            {flight}
+            {energy}
+            {holds}
+            <div ref={range_table_ref}>
+                <RangeTable rows={range_rows} units={units} wind_holds={(*wind_hold_rows).clone()} reference_wind={state.tables.reference_wind} lag_times={(*lag_rows).clone()} />
+            </div>
+            if !bracket_rows.is_empty() {
+                <div>{format!(
+                    "Bracketing {:.0} {} in steps of {:.0}",
+                    distance(state.tables.bracket_center),
+                    distance_unit,
+                    distance(state.tables.bracket_step),
+                )}</div>
+                <RangeCard
+                    rows={range_card(&bracket_rows, state.click_value, state.click_unit)}
+                    units={units}
+                    click_value={state.click_value}
+                    click_unit={state.click_unit}
+                />
+            }
+            <LateralTable rows={tables.1.clone()} units={units} />
+            if let Some(rows) = table_b.as_ref() {
+                <ComparisonTable rows={compare_tables(&tables.0, rows)} units={units} />
+            }
+            <button type="button" onclick={on_download_csv}>{"Download CSV"}</button>
+            <button type="button" onclick={on_copy_table}>{"Copy to clipboard"}</button>
+            if *table_selected {
+                <div class="warning">{"Couldn't reach the clipboard: the table is selected, press Ctrl+C to copy it."}</div>
+            }
+            <button type="button" onclick={on_print_view}>{"Print view"}</button>
+            <LineChart
+                series={trajectory_series}
+                x_label={labelled("Range", Quantity::Distance)}
+                y_label={labelled(&format!("Height above {}", state.frame.label().to_lowercase()), Quantity::Distance)}
+            />
+            <LineChart
+                series={velocity_series}
+                x_label={labelled("Range", Quantity::Distance)}
+                y_label={labelled("Velocity", Quantity::Velocity)}
+                markers={mach_markers}
+            />
+            <LineChart
+                series={energy_series}
+                x_label={labelled("Range", Quantity::Distance)}
+                y_label="Energy (J)"
+                markers={energy_markers}
+            />
+            <LineChart
+                series={drag_series}
+                x_label="Mach"
+                y_label={format!("{} drag coefficient", params.drag_model.label())}
+                markers={current_mach}
+            />
+        </div>
+    }
+}
@@ -0,0 +1,133 @@
+/// ICAO standard sea-level temperature in °C.
+pub const STANDARD_TEMPERATURE: f64 = 15.0;
+/// ICAO standard sea-level pressure in hPa.
+pub const STANDARD_PRESSURE: f64 = 1013.25;
+
+/// Specific gas constant for dry air, J/(kg·K).
+const DRY_AIR_GAS_CONSTANT: f64 = 287.05;
+/// Specific gas constant for water vapor, J/(kg·K).
+const WATER_VAPOR_GAS_CONSTANT: f64 = 461.495;
+/// Tropospheric temperature lapse rate, K/m.
+const LAPSE_RATE: f64 = 0.0065;
+const CELSIUS_TO_KELVIN: f64 = 273.15;
+/// `g / (R * L)`, the exponent of the barometric formula.
+const BAROMETRIC_EXPONENT: f64 = 5.25588;
+
+/// Temperature in °C at `altitude_m`, given the sea-level temperature.
+pub fn temperature_at(altitude_m: f64, temp_c: f64) -> f64 {
+    temp_c - LAPSE_RATE * altitude_m
+}
+
+/// Speed of sound in dry air, m/s.
+pub fn speed_of_sound(temp_c: f64) -> f64 {
+    331.3 * (1.0 + temp_c / CELSIUS_TO_KELVIN).sqrt()
+}
+
+/// Saturation vapor pressure over water in hPa (Magnus formula).
+pub fn saturation_vapor_pressure(temp_c: f64) -> f64 {
+    6.1094 * (17.625 * temp_c / (temp_c + 243.04)).exp()
+}
+
+/// Air density in kg/m³ at `altitude_m` above sea level.
+///
+/// `temp_c` and `pressure_hpa` are the sea-level conditions; the ICAO lapse
+/// rate carries them up to the requested altitude. `humidity` is relative
+/// humidity in percent, and the water vapor it implies displaces heavier
+/// dry air.
+pub fn air_density(altitude_m: f64, temp_c: f64, pressure_hpa: f64, humidity: f64) -> f64 {
+    let sea_level_temperature = temp_c + CELSIUS_TO_KELVIN;
+    let temperature = temperature_at(altitude_m, temp_c) + CELSIUS_TO_KELVIN;
+    let pressure =
+        pressure_hpa * 100.0 * (temperature / sea_level_temperature).powf(BAROMETRIC_EXPONENT);
+
+    let vapor_pressure = humidity.clamp(0.0, 100.0) / 100.0
+        * saturation_vapor_pressure(temperature - CELSIUS_TO_KELVIN)
+        * 100.0;
+    let dry_pressure = pressure - vapor_pressure;
+
+    dry_pressure / (DRY_AIR_GAS_CONSTANT * temperature)
+        + vapor_pressure / (WATER_VAPOR_GAS_CONSTANT * temperature)
+}
+
+/// Density altitude in meters: the altitude at which a standard day, with
+/// dry air, has the given `density` in kg/m³.
+pub fn density_altitude(density: f64) -> f64 {
+    let standard = air_density(0.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+    // Standard-day density falls as (T / T0)^(exponent - 1) going up.
+    let temperature_ratio = (density / standard).powf(1.0 / (BAROMETRIC_EXPONENT - 1.0));
+    (STANDARD_TEMPERATURE + CELSIUS_TO_KELVIN) * (1.0 - temperature_ratio) / LAPSE_RATE
+}
+
+/// The sea-level pressure in hPa that gives `density` at `altitude_m`, with
+/// the other conditions as they are. Density is linear in pressure, so two
+/// samples pin it down.
+pub fn pressure_for_density(density: f64, altitude_m: f64, temp_c: f64, humidity: f64) -> f64 {
+    let at = |pressure_hpa| air_density(altitude_m, temp_c, pressure_hpa, humidity);
+    let offset = at(0.0);
+    let per_hpa = at(STANDARD_PRESSURE) - offset;
+    STANDARD_PRESSURE * (density - offset) / per_hpa
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_sea_level_density() {
+        let density = air_density(0.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+        assert!((density - 1.225).abs() < 1e-3, "{density}");
+    }
+
+    #[test]
+    fn density_drops_with_altitude() {
+        let sea_level = air_density(0.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+        let high = air_density(2000.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+        // ICAO gives 1.0066 kg/m³ at 2000 m.
+        assert!((high - 1.0066).abs() < 1e-3, "{high}");
+        assert!(high < sea_level * 0.85);
+    }
+
+    #[test]
+    fn humid_air_is_less_dense() {
+        let dry = air_density(0.0, 30.0, STANDARD_PRESSURE, 0.0);
+        let humid = air_density(0.0, 30.0, STANDARD_PRESSURE, 90.0);
+        assert!(humid < dry);
+        assert!(dry - humid < 0.03, "dry {dry} humid {humid}");
+    }
+
+    #[test]
+    fn saturation_vapor_pressure_at_known_points() {
+        assert!((saturation_vapor_pressure(0.0) - 6.11).abs() < 0.01);
+        assert!((saturation_vapor_pressure(30.0) - 42.4).abs() < 0.2);
+    }
+
+    #[test]
+    fn speed_of_sound_on_a_standard_day() {
+        assert!((speed_of_sound(STANDARD_TEMPERATURE) - 340.0).abs() < 0.5);
+        assert!(speed_of_sound(-20.0) < speed_of_sound(30.0));
+    }
+
+    #[test]
+    fn standard_day_is_at_zero_density_altitude() {
+        let density = air_density(0.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+        let feet = density_altitude(density) / 0.3048;
+        assert!(feet.abs() < 1.0, "{feet}");
+    }
+
+    #[test]
+    fn density_altitude_follows_hot_and_high_air() {
+        let high = air_density(2000.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+        assert!((density_altitude(high) - 2000.0).abs() < 1.0);
+        // 35 °C at sea level flies like about 2200 ft up.
+        let hot = air_density(0.0, 35.0, STANDARD_PRESSURE, 0.0);
+        let feet = density_altitude(hot) / 0.3048;
+        assert!((2000.0..2600.0).contains(&feet), "{feet}");
+    }
+
+    #[test]
+    fn pressure_for_density_round_trips() {
+        let density = air_density(1500.0, 25.0, 1005.0, 60.0);
+        let pressure = pressure_for_density(density, 1500.0, 25.0, 60.0);
+        assert!((pressure - 1005.0).abs() < 1e-6, "{pressure}");
+    }
+}
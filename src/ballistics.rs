@@ -0,0 +1,1883 @@
+use std::ops::{Add, AddAssign, Mul, Sub};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::atmosphere::{self, STANDARD_PRESSURE, STANDARD_TEMPERATURE};
+use crate::drag::{self, BcBands, DragModel};
+use crate::error::BallisticError;
+use crate::spin::{self, TwistDirection};
+use crate::validation;
+use crate::wind::{WindProfile, WindSample};
+
+pub const GRAVITY: f64 = 9.80665;
+/// Default integration step in seconds.
+pub const TIME_STEP: f64 = 0.01;
+/// Range of integration steps the calculator accepts, in seconds.
+pub const MIN_TIME_STEP: f64 = 0.0005;
+pub const MAX_TIME_STEP: f64 = 0.05;
+/// Fraction of its speed a shot can lose to drag in one step before the
+/// integration goes noticeably wrong.
+pub const MAX_DRAG_LOSS_PER_STEP: f64 = 0.02;
+/// Ceiling on integration steps for a shot with no flight-time cap; over
+/// eight minutes of flight at the finest step. A capped shot takes as many
+/// steps as its cap needs.
+pub const MAX_STEPS: usize = 1_000_000;
+/// Default cap on flight time, in seconds; longer than any aimed shot.
+pub const DEFAULT_MAX_FLIGHT_TIME: f64 = 120.0;
+/// Default cap on range, in meters.
+pub const DEFAULT_MAX_RANGE: f64 = 10_000.0;
+/// Earth's sidereal rotation rate in rad/s.
+pub const EARTH_ANGULAR_VELOCITY: f64 = 7.292e-5;
+/// Magnus acceleration in m/s² per rad/s of spin per m/s of crosswind. A
+/// 160,000 rpm bullet in a 10 mph crosswind gets about 0.01 m/s².
+pub const MAGNUS_COEFFICIENT: f64 = 1.3e-7;
+/// Mach number below which a supersonic bullet starts to feel transonic
+/// buffeting.
+pub const TRANSONIC_MACH: f64 = 1.2;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vector2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// World-frame vector: x downrange, y up and z to the shooter's right.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Component-wise `+`, `-`, `+=` and scaling by `f64` for a vector type.
+macro_rules! vector_ops {
+    ($vector:ident { $($field:ident),+ }) => {
+        impl Add for $vector {
+            type Output = $vector;
+
+            fn add(self, other: $vector) -> $vector {
+                $vector { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $vector {
+            type Output = $vector;
+
+            fn sub(self, other: $vector) -> $vector {
+                $vector { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Mul<f64> for $vector {
+            type Output = $vector;
+
+            fn mul(self, scale: f64) -> $vector {
+                $vector { $($field: self.$field * scale),+ }
+            }
+        }
+
+        impl AddAssign for $vector {
+            fn add_assign(&mut self, other: $vector) {
+                *self = *self + other;
+            }
+        }
+
+        impl $vector {
+            pub fn magnitude(self) -> f64 {
+                (0.0 $(+ self.$field * self.$field)+).sqrt()
+            }
+        }
+    };
+}
+
+vector_ops!(Vector2 { x, y });
+vector_ops!(Vector3 { x, y, z });
+
+impl Vector3 {
+    pub fn dot(self, other: Vector3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Vector3) -> Vector3 {
+        Vector3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Projectile {
+    pub position: Vector3,
+    pub velocity: Vector3,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Integrator {
+    /// Semi-implicit Euler, kept for comparison.
+    Euler,
+    /// Classic fourth-order Runge-Kutta.
+    #[default]
+    Rk4,
+}
+
+impl Integrator {
+    pub const ALL: [Integrator; 2] = [Integrator::Euler, Integrator::Rk4];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Integrator::Euler => "Euler",
+            Integrator::Rk4 => "RK4",
+        }
+    }
+}
+
+impl FromStr for Integrator {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Integrator::ALL
+            .into_iter()
+            .find(|integrator| integrator.label() == s)
+            .ok_or(())
+    }
+}
+
+/// Missing fields fall back to their defaults so older saved profiles load.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShotParams {
+    /// Wind speed in m/s.
+    pub wind: f64,
+    /// Direction the wind blows from, in degrees clockwise from the firing
+    /// line: 0 is a headwind, 90 comes from the shooter's right.
+    pub wind_direction: f64,
+    /// Wind by height; when present it replaces `wind` and `wind_direction`.
+    pub wind_profile: WindProfile,
+    /// Launch angle in degrees above horizontal.
+    pub elevation: f64,
+    pub caliber: f64,
+    pub ballistic_coefficient: f64,
+    /// Velocity-stepped coefficients; when present they replace
+    /// `ballistic_coefficient`.
+    pub bc_bands: BcBands,
+    /// Muzzle velocity in m/s with the powder at `reference_temperature`.
+    pub muzzle_velocity: f64,
+    /// Change in muzzle velocity per °C of powder temperature, in m/s.
+    pub velocity_temp_sensitivity: f64,
+    /// Temperature in °C the muzzle velocity was measured at.
+    pub reference_temperature: f64,
+    pub drag_model: DragModel,
+    /// Shooter latitude in degrees, positive north.
+    pub latitude: f64,
+    /// Firing direction in degrees clockwise from true north.
+    pub azimuth: f64,
+    /// Barrel twist in inches per turn.
+    pub twist_rate: f64,
+    pub twist_direction: TwistDirection,
+    pub bullet_length: f64,
+    pub mass: f64,
+    /// Firing point altitude in meters above sea level.
+    pub altitude: f64,
+    /// Sea-level temperature in °C.
+    pub temperature: f64,
+    /// Sea-level pressure in hPa.
+    pub pressure: f64,
+    /// Relative humidity in percent.
+    pub humidity: f64,
+    pub integrator: Integrator,
+    /// With drag off the shot flies in a vacuum: no drag, wind or Coriolis.
+    pub drag_enabled: bool,
+    /// Whether the spinning bullet feels a Magnus force from the crosswind.
+    /// Off by default, as it's tiny and easier to judge on its own.
+    pub magnus_enabled: bool,
+    /// Whether Coriolis acts vertically as well as sideways. This is the
+    /// Eötvös effect: firing east makes the bullet a little lighter, firing
+    /// west a little heavier.
+    pub eotvos_enabled: bool,
+    /// Integration step in seconds. Smaller is more accurate and slower.
+    pub time_step: f64,
+    /// Integration stops after this many seconds even if the shot is still
+    /// in the air; see [`truncated`].
+    pub max_flight_time: f64,
+    /// Integration stops this many meters downrange even if the shot is
+    /// still in the air.
+    pub max_range: f64,
+}
+
+impl Default for ShotParams {
+    fn default() -> Self {
+        ShotParams {
+            wind: 0.0,
+            wind_direction: 0.0,
+            wind_profile: WindProfile::default(),
+            elevation: 0.0,
+            caliber: 0.00762,
+            ballistic_coefficient: 0.4,
+            bc_bands: BcBands::default(),
+            muzzle_velocity: 850.0,
+            velocity_temp_sensitivity: 0.0,
+            reference_temperature: STANDARD_TEMPERATURE,
+            drag_model: DragModel::G1,
+            latitude: 0.0,
+            azimuth: 0.0,
+            twist_rate: 10.0,
+            twist_direction: TwistDirection::Right,
+            bullet_length: 0.0309,
+            mass: 0.010886,
+            altitude: 0.0,
+            temperature: STANDARD_TEMPERATURE,
+            pressure: STANDARD_PRESSURE,
+            humidity: 0.0,
+            integrator: Integrator::Rk4,
+            drag_enabled: true,
+            magnus_enabled: false,
+            eotvos_enabled: true,
+            time_step: TIME_STEP,
+            max_flight_time: DEFAULT_MAX_FLIGHT_TIME,
+            max_range: DEFAULT_MAX_RANGE,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrajectoryPoint {
+    pub time: f64,
+    pub position: Vector3,
+    pub velocity: Vector3,
+    /// How much of `position.z` comes from Coriolis alone, positive to the
+    /// right.
+    #[serde(default)]
+    pub coriolis_drift: f64,
+    /// How much of `position.z` comes from the wind alone, positive to the
+    /// right.
+    #[serde(default)]
+    pub wind_drift: f64,
+}
+
+impl ShotParams {
+    /// Muzzle velocity corrected for the powder being at the firing point's
+    /// temperature rather than the reference one.
+    pub fn effective_muzzle_velocity(&self) -> f64 {
+        let temperature = atmosphere::temperature_at(self.altitude, self.temperature);
+        self.muzzle_velocity
+            + self.velocity_temp_sensitivity * (temperature - self.reference_temperature)
+    }
+
+    /// Miller stability factor at the muzzle, corrected for the launch
+    /// velocity and the air at the firing point.
+    pub fn stability(&self) -> f64 {
+        let sg =
+            spin::miller_stability(self.mass, self.caliber, self.bullet_length, self.twist_rate);
+        let density = atmosphere::air_density(
+            self.altitude,
+            self.temperature,
+            self.pressure,
+            self.humidity,
+        );
+        spin::corrected_stability(sg, self.effective_muzzle_velocity(), density)
+    }
+
+    /// Density altitude of the air at the firing point, in meters.
+    pub fn density_altitude(&self) -> f64 {
+        atmosphere::density_altitude(atmosphere::air_density(
+            self.altitude,
+            self.temperature,
+            self.pressure,
+            self.humidity,
+        ))
+    }
+
+    /// Local speed of sound at `height` meters above the firing point.
+    pub fn speed_of_sound_at(&self, height: f64) -> f64 {
+        let altitude = self.altitude + height;
+        atmosphere::speed_of_sound(atmosphere::temperature_at(altitude, self.temperature))
+    }
+
+    /// The same shot with the flight-time and range caps lifted, leaving only
+    /// [`MAX_STEPS`], for searches that need to know where it really lands.
+    pub fn uncapped(&self) -> ShotParams {
+        ShotParams {
+            max_flight_time: f64::INFINITY,
+            max_range: f64::INFINITY,
+            ..*self
+        }
+    }
+}
+
+pub fn drag_force(
+    v: f64,
+    caliber: f64,
+    ballistic_coefficient: f64,
+    drag_model: DragModel,
+    air_density: f64,
+    speed_of_sound: f64,
+) -> Vector3 {
+    let deceleration = drag::deceleration(
+        drag_model,
+        v,
+        caliber,
+        ballistic_coefficient,
+        air_density,
+        speed_of_sound,
+    );
+    Vector3 {
+        x: -deceleration,
+        y: -deceleration,
+        z: -deceleration,
+    }
+}
+
+/// Earth's rotation vector expressed in the shooter's frame.
+pub fn earth_rotation(latitude: f64, azimuth: f64) -> Vector3 {
+    let latitude = latitude.to_radians();
+    let azimuth = azimuth.to_radians();
+    Vector3 {
+        x: EARTH_ANGULAR_VELOCITY * latitude.cos() * azimuth.cos(),
+        y: EARTH_ANGULAR_VELOCITY * latitude.sin(),
+        z: -EARTH_ANGULAR_VELOCITY * latitude.cos() * azimuth.sin(),
+    }
+}
+
+/// The Coriolis acceleration `-2 Ω × v`.
+pub fn coriolis_acceleration(omega: Vector3, velocity: Vector3) -> Vector3 {
+    omega.cross(velocity) * -2.0
+}
+
+/// Velocity of the air for a wind of `speed` m/s blowing from `direction`
+/// degrees clockwise of the firing line.
+pub fn wind_velocity(speed: f64, direction: f64) -> Vector3 {
+    let direction = direction.to_radians();
+    Vector3 {
+        x: -speed * direction.cos(),
+        y: 0.0,
+        z: -speed * direction.sin(),
+    }
+}
+
+/// The air's velocity `height` meters above the firing point.
+fn wind_at(params: &ShotParams, height: f64) -> Vector3 {
+    params
+        .wind_profile
+        .at(height)
+        .unwrap_or_else(|| wind_velocity(params.wind, params.wind_direction))
+}
+
+/// Magnus acceleration on a bullet spinning at `spin_rate` rpm about its
+/// direction of flight, from the `wind` blowing across it. With a right-hand
+/// twist, wind from the left lifts it.
+pub fn magnus_acceleration(
+    spin_rate: f64,
+    velocity: Vector3,
+    wind: Vector3,
+    direction: TwistDirection,
+) -> Vector3 {
+    let speed = velocity.magnitude();
+    if speed == 0.0 {
+        return Vector3::default();
+    }
+    let omega = spin_rate * 2.0 * std::f64::consts::PI / 60.0;
+    // The spin axis crossed with the airspeed `velocity - wind`; the axis
+    // lies along `velocity`, so only the wind is left.
+    let axis = velocity * (1.0 / speed);
+    axis.cross(wind) * (-MAGNUS_COEFFICIENT * omega * direction.sign())
+}
+
+/// The wind at the projectile, and drag's deceleration per m/s of airspeed:
+/// drag acts against the bullet's motion through the air, not the ground,
+/// as `-(velocity - wind) * k`.
+fn drag_per_airspeed(projectile: &Projectile, params: &ShotParams) -> (Vector3, f64) {
+    let wind = wind_at(params, projectile.position.y);
+    let v = (projectile.velocity - wind).magnitude();
+    if v == 0.0 {
+        return (wind, 0.0);
+    }
+
+    let altitude = params.altitude + projectile.position.y;
+    let air_density = atmosphere::air_density(
+        altitude,
+        params.temperature,
+        params.pressure,
+        params.humidity,
+    );
+    let speed_of_sound = params.speed_of_sound_at(projectile.position.y);
+    let drag = drag_force(
+        v,
+        params.caliber,
+        params
+            .bc_bands
+            .at(v)
+            .unwrap_or(params.ballistic_coefficient),
+        params.drag_model,
+        air_density,
+        speed_of_sound,
+    );
+    (wind, -drag.x / v)
+}
+
+/// The part of a wind blowing straight down the firing line at the shooter:
+/// positive for a headwind, negative for a tailwind.
+pub fn headwind(speed: f64, direction: f64) -> f64 {
+    -wind_velocity(speed, direction).x
+}
+
+/// The part of a wind blowing across the firing line, positive to the right.
+pub fn crosswind(speed: f64, direction: f64) -> f64 {
+    wind_velocity(speed, direction).z
+}
+
+/// The same wind with its head or tail component taken away.
+fn across_only(speed: f64, direction: f64) -> (f64, f64) {
+    let across = crosswind(speed, direction);
+    // Blowing to the right means coming from the left, at 270°.
+    (across.abs(), if across > 0.0 { 270.0 } else { 90.0 })
+}
+
+/// How much shorter the shot lands than it would if the wind, and every
+/// sample of its profile, only blew across the firing line. Negative when a
+/// tailwind carries it further. The caps are lifted for both shots; fails
+/// with [`BallisticError::OutOfRange`] if either still hasn't landed.
+pub fn headwind_range_cost(params: &ShotParams) -> Result<f64, BallisticError> {
+    let (wind, wind_direction) = across_only(params.wind, params.wind_direction);
+    let samples: Vec<WindSample> = params
+        .wind_profile
+        .iter()
+        .map(|sample| {
+            let (speed, direction) = across_only(sample.speed, sample.direction);
+            WindSample {
+                speed,
+                direction,
+                ..sample
+            }
+        })
+        .collect();
+    let calm = ShotParams {
+        wind,
+        wind_direction,
+        wind_profile: WindProfile::from_slice(&samples),
+        ..*params
+    };
+    let range = |params: &ShotParams| {
+        let points = simulate(&params.uncapped())?;
+        landing_point(&points)
+            .map(|point| point.position.x)
+            .ok_or(BallisticError::OutOfRange)
+    };
+    Ok(range(&calm)? - range(params)?)
+}
+
+/// How much higher the Eötvös effect alone puts the shot at `range` meters,
+/// negative when it drops it. `None` if the shot never gets that far.
+pub fn eotvos_lift(params: &ShotParams, range: f64) -> Option<f64> {
+    let height = |eotvos_enabled| {
+        let params = ShotParams {
+            eotvos_enabled,
+            ..*params
+        };
+        point_at_range(&simulate_to_range(&params, range), range).map(|point| point.position.y)
+    };
+    Some(height(true)? - height(false)?)
+}
+
+/// Total acceleration on a projectile from wind, drag, Coriolis and gravity.
+pub fn acceleration(projectile: &Projectile, params: &ShotParams) -> Vector3 {
+    if !params.drag_enabled {
+        return Vector3 {
+            x: 0.0,
+            y: -GRAVITY,
+            z: 0.0,
+        };
+    }
+
+    let velocity = projectile.velocity;
+    let (wind, k) = drag_per_airspeed(projectile, params);
+    let gravity = Vector3 {
+        x: 0.0,
+        y: -GRAVITY,
+        z: 0.0,
+    };
+    let mut coriolis =
+        coriolis_acceleration(earth_rotation(params.latitude, params.azimuth), velocity);
+    if !params.eotvos_enabled {
+        coriolis.y = 0.0;
+    }
+    (velocity - wind) * -k + coriolis + gravity
+}
+
+pub fn update_velocity(projectile: &mut Projectile, dt: f64, params: &ShotParams) {
+    projectile.velocity += acceleration(projectile, params) * dt;
+}
+
+pub fn update_position(projectile: &mut Projectile, dt: f64) {
+    projectile.position += projectile.velocity * dt;
+}
+
+/// Advances a projectile by one fourth-order Runge-Kutta step.
+pub fn integrate_rk4(projectile: &mut Projectile, dt: f64, params: &ShotParams) {
+    let derivative = |position: Vector3, velocity: Vector3| {
+        let state = Projectile { position, velocity };
+        (velocity, acceleration(&state, params))
+    };
+    let Projectile { position, velocity } = *projectile;
+
+    let (dx1, dv1) = derivative(position, velocity);
+    let (dx2, dv2) = derivative(position + dx1 * (dt / 2.0), velocity + dv1 * (dt / 2.0));
+    let (dx3, dv3) = derivative(position + dx2 * (dt / 2.0), velocity + dv2 * (dt / 2.0));
+    let (dx4, dv4) = derivative(position + dx3 * dt, velocity + dv3 * dt);
+
+    let blend =
+        |a: Vector3, b: Vector3, c: Vector3, d: Vector3| (a + (b + c) * 2.0 + d) * (1.0 / 6.0);
+    projectile.position = position + blend(dx1, dx2, dx3, dx4) * dt;
+    projectile.velocity = velocity + blend(dv1, dv2, dv3, dv4) * dt;
+}
+
+/// Advances a projectile by `dt` with the integrator chosen in `params`.
+pub fn step(projectile: &mut Projectile, dt: f64, params: &ShotParams) {
+    match params.integrator {
+        Integrator::Euler => {
+            update_velocity(projectile, dt, params);
+            update_position(projectile, dt);
+        }
+        Integrator::Rk4 => integrate_rk4(projectile, dt, params),
+    }
+}
+
+pub fn launch_velocity(muzzle_velocity: f64, elevation: f64) -> Vector3 {
+    let angle = elevation.to_radians();
+    Vector3 {
+        x: muzzle_velocity * angle.cos(),
+        y: muzzle_velocity * angle.sin(),
+        z: 0.0,
+    }
+}
+
+/// Integrates a shot from the muzzle until it comes back down to y = 0, or
+/// until it passes `max_flight_time` or `max_range`, or [`MAX_STEPS`] have
+/// elapsed with no time cap. Fails if [`validation::check`] rejects
+/// `params`.
+pub fn simulate(params: &ShotParams) -> Result<Vec<TrajectoryPoint>, BallisticError> {
+    validation::check(params)?;
+    Ok(integrate(params, |projectile| projectile.position.y <= 0.0))
+}
+
+/// Integrates a shot until it is `range` meters downrange, ignoring the
+/// ground. Flat-fire trajectories dip below muzzle height long before the
+/// ranges they are zeroed and dialed for.
+pub fn simulate_to_range(params: &ShotParams, range: f64) -> Vec<TrajectoryPoint> {
+    integrate(params, |projectile| {
+        projectile.position.x >= range || projectile.velocity.x <= 0.0
+    })
+}
+
+fn integrate(params: &ShotParams, done: impl Fn(&Projectile) -> bool) -> Vec<TrajectoryPoint> {
+    let mut projectile = Projectile {
+        position: Vector3::default(),
+        velocity: launch_velocity(params.effective_muzzle_velocity(), params.elevation),
+    };
+    let omega = earth_rotation(params.latitude, params.azimuth);
+    let muzzle_rpm = spin::muzzle_spin_rate(params.effective_muzzle_velocity(), params.twist_rate);
+    let mut time = 0.0;
+    // Coriolis' and the wind's shares of the lateral motion, followed
+    // alongside the shot so the deflection can be broken down by cause
+    // afterwards. Drag is linear in airspeed, so each share slows on its own.
+    let mut coriolis_speed = 0.0;
+    let mut coriolis_drift = 0.0;
+    let mut wind_speed = 0.0;
+    let mut wind_drift = 0.0;
+    let mut points = vec![TrajectoryPoint {
+        time,
+        position: projectile.position,
+        velocity: projectile.velocity,
+        coriolis_drift,
+        wind_drift,
+    }];
+
+    // Enough steps to reach the time cap whatever the step size, with one to
+    // spare for rounding in the summed time.
+    let max_steps = if params.max_flight_time.is_finite() {
+        (params.max_flight_time / params.time_step).ceil() as usize + 1
+    } else {
+        MAX_STEPS
+    };
+    for _ in 0..max_steps {
+        step(&mut projectile, params.time_step, params);
+        time += params.time_step;
+        // Spin decays with time, which the integrators don't track, so the
+        // Magnus force is applied as a kick after each step.
+        if params.magnus_enabled && params.drag_enabled {
+            let magnus = magnus_acceleration(
+                spin::spin_rate(muzzle_rpm, time),
+                projectile.velocity,
+                wind_at(params, projectile.position.y),
+                params.twist_direction,
+            );
+            projectile.velocity += magnus * params.time_step;
+        }
+        let (previous_coriolis, previous_wind) = (coriolis_speed, wind_speed);
+        if params.drag_enabled {
+            let (wind, k) = drag_per_airspeed(&projectile, params);
+            let coriolis = coriolis_acceleration(omega, projectile.velocity).z;
+            coriolis_speed += (coriolis - k * coriolis_speed) * params.time_step;
+            wind_speed += k * (wind.z - wind_speed) * params.time_step;
+        }
+        // Averaging each speed over the step keeps the shares in line with
+        // the integrated position.
+        coriolis_drift += 0.5 * (previous_coriolis + coriolis_speed) * params.time_step;
+        wind_drift += 0.5 * (previous_wind + wind_speed) * params.time_step;
+
+        points.push(TrajectoryPoint {
+            time,
+            position: projectile.position,
+            velocity: projectile.velocity,
+            coriolis_drift,
+            wind_drift,
+        });
+
+        let cut_off = time >= params.max_flight_time || projectile.position.x >= params.max_range;
+        if done(&projectile) || cut_off {
+            break;
+        }
+    }
+
+    points
+}
+
+/// Linearly interpolates the trajectory to the point where it reaches
+/// `range` meters downrange, or `None` if it never gets that far.
+pub fn point_at_range(points: &[TrajectoryPoint], range: f64) -> Option<TrajectoryPoint> {
+    let pair = points
+        .windows(2)
+        .find(|pair| pair[0].position.x <= range && range <= pair[1].position.x)?;
+    let (a, b) = (&pair[0], &pair[1]);
+    let span = b.position.x - a.position.x;
+    let t = if span > 0.0 {
+        (range - a.position.x) / span
+    } else {
+        0.0
+    };
+    let mut point = lerp_points(a, b, t);
+    point.position.x = range;
+    Some(point)
+}
+
+/// Interpolates the step from `before` (above ground) to `after` (at or
+/// below it) to the point where the shot crosses y = 0.
+pub fn ground_impact(before: &TrajectoryPoint, after: &TrajectoryPoint) -> TrajectoryPoint {
+    let fall = before.position.y - after.position.y;
+    let t = if fall > 0.0 {
+        before.position.y / fall
+    } else {
+        1.0
+    };
+    let mut point = lerp_points(before, after, t.clamp(0.0, 1.0));
+    point.position.y = 0.0;
+    point
+}
+
+/// Blends every field of two trajectory points, `t = 0` giving `a`.
+pub fn lerp_points(a: &TrajectoryPoint, b: &TrajectoryPoint, t: f64) -> TrajectoryPoint {
+    TrajectoryPoint {
+        time: a.time + (b.time - a.time) * t,
+        position: a.position + (b.position - a.position) * t,
+        velocity: a.velocity + (b.velocity - a.velocity) * t,
+        coriolis_drift: a.coriolis_drift + (b.coriolis_drift - a.coriolis_drift) * t,
+        wind_drift: a.wind_drift + (b.wind_drift - a.wind_drift) * t,
+    }
+}
+
+/// The point on the straight step from `a` to `b` nearest `target`, and how
+/// far from it that is. A fast shot can jump right past a small target
+/// between samples, so this checks the whole step rather than its ends.
+pub fn closest_approach(
+    a: &TrajectoryPoint,
+    b: &TrajectoryPoint,
+    target: Vector3,
+) -> (TrajectoryPoint, f64) {
+    let step = b.position - a.position;
+    let length = step.dot(step);
+    let t = if length > 0.0 {
+        ((target - a.position).dot(step) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let point = lerp_points(a, b, t);
+    (point, (target - point.position).magnitude())
+}
+
+/// The closest the whole trajectory comes to `target`.
+pub fn miss_distance(
+    points: &[TrajectoryPoint],
+    target: Vector3,
+) -> Option<(TrajectoryPoint, f64)> {
+    points
+        .windows(2)
+        .map(|pair| closest_approach(&pair[0], &pair[1], target))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Where a [`simulate`]d shot comes back down to y = 0, interpolated within
+/// the final step. `None` if it never reached the ground.
+pub fn landing_point(points: &[TrajectoryPoint]) -> Option<TrajectoryPoint> {
+    match points {
+        [.., before, after] if after.position.y <= 0.0 => Some(ground_impact(before, after)),
+        _ => None,
+    }
+}
+
+/// Whether a [`simulate`]d shot was cut off by one of the caps while still in
+/// the air, rather than coming back down.
+pub fn truncated(points: &[TrajectoryPoint]) -> bool {
+    !points.is_empty() && landing_point(points).is_none()
+}
+
+/// Like [`simulate`], but with the final step cut short exactly where the
+/// shot lands, ready to be played back.
+pub fn simulate_to_impact(params: &ShotParams) -> Result<Vec<TrajectoryPoint>, BallisticError> {
+    let mut points = simulate(params)?;
+    if let Some(landing) = landing_point(&points) {
+        let last = points.len() - 1;
+        points[last] = landing;
+    }
+    Ok(points)
+}
+
+/// Highest point of the trajectory.
+pub fn max_ordinate(points: &[TrajectoryPoint]) -> Option<TrajectoryPoint> {
+    points
+        .iter()
+        .copied()
+        .max_by(|a, b| a.position.y.total_cmp(&b.position.y))
+}
+
+/// Terminal ballistics where the shot lands.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Impact {
+    /// Speed in m/s.
+    pub velocity: f64,
+    /// Kinetic energy in joules.
+    pub energy: f64,
+    /// Angle below horizontal in degrees.
+    pub angle_of_fall: f64,
+}
+
+/// Kinetic energy in joules of `mass` kg moving at `speed` m/s.
+pub fn kinetic_energy(mass: f64, speed: f64) -> f64 {
+    0.5 * mass * speed.powi(2)
+}
+
+pub fn impact(point: &TrajectoryPoint, mass: f64) -> Impact {
+    let velocity = point.velocity;
+    let speed = velocity.magnitude();
+    Impact {
+        velocity: speed,
+        energy: kinetic_energy(mass, speed),
+        angle_of_fall: (-velocity.y).atan2(velocity.x).to_degrees(),
+    }
+}
+
+/// Height of `point` above a level line of sight `sight_height` above the
+/// bore at the muzzle.
+pub fn height_above_sight_line(point: &TrajectoryPoint, sight_height: f64) -> f64 {
+    point.position.y - sight_height
+}
+
+/// Which line heights and drops are measured from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Frame {
+    /// The level line of sight: where the shooter sees the bullet go.
+    #[default]
+    LineOfSight,
+    /// The line the bore points along, which the bullet only ever falls away
+    /// from.
+    Bore,
+}
+
+impl Frame {
+    pub fn toggle(self) -> Self {
+        match self {
+            Frame::LineOfSight => Frame::Bore,
+            Frame::Bore => Frame::LineOfSight,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Frame::LineOfSight => "Line of sight",
+            Frame::Bore => "Bore",
+        }
+    }
+
+    /// Height of the line this frame measures from, `range` meters out, for a
+    /// bore launched at `elevation` degrees with the sight line `sight_height`
+    /// above it at the muzzle.
+    pub fn reference_height(self, range: f64, elevation: f64, sight_height: f64) -> f64 {
+        match self {
+            Frame::LineOfSight => sight_height,
+            Frame::Bore => range * elevation.to_radians().tan(),
+        }
+    }
+}
+
+/// Height of `point` above the line `frame` measures from.
+pub fn height_in_frame(
+    point: &TrajectoryPoint,
+    frame: Frame,
+    elevation: f64,
+    sight_height: f64,
+) -> f64 {
+    point.position.y - frame.reference_height(point.position.x, elevation, sight_height)
+}
+
+/// Range at which the shot first slows below `mach` times the local speed of
+/// sound, interpolated within the step. `None` if it never does.
+pub fn range_at_mach(points: &[TrajectoryPoint], params: &ShotParams, mach: f64) -> Option<f64> {
+    slows_below(points, |point| {
+        mach * params.speed_of_sound_at(point.position.y)
+    })
+}
+
+/// Range at which a supersonic shot slows into the transonic band, for air
+/// at `temperature` °C. `None` if it stays above it or never started there.
+pub fn transonic_range(points: &[TrajectoryPoint], temperature: f64) -> Option<f64> {
+    let threshold = TRANSONIC_MACH * atmosphere::speed_of_sound(temperature);
+    slows_below(points, |_| threshold)
+}
+
+/// Fraction of its speed the shot loses to drag over its first step, the
+/// fastest it slows. Past [`MAX_DRAG_LOSS_PER_STEP`] the step is too coarse.
+pub fn drag_loss_per_step(params: &ShotParams) -> f64 {
+    if !params.drag_enabled {
+        return 0.0;
+    }
+    let muzzle = Projectile {
+        position: Vector3::default(),
+        velocity: launch_velocity(params.effective_muzzle_velocity(), params.elevation),
+    };
+    let (_, k) = drag_per_airspeed(&muzzle, params);
+    k * params.time_step
+}
+
+/// How far the shot stays supersonic: the range at which it first slows to
+/// Mach 1 in the air around it. `None` if it never does, which includes
+/// leaving the muzzle subsonic.
+pub fn supersonic_range(points: &[TrajectoryPoint], params: &ShotParams) -> Option<f64> {
+    range_at_mach(points, params, 1.0)
+}
+
+/// Range at which a bullet of `mass` kg first carries less than `energy`
+/// joules, interpolated within the step. `None` if it never does.
+pub fn range_below_energy(points: &[TrajectoryPoint], mass: f64, energy: f64) -> Option<f64> {
+    let speed = (2.0 * energy / mass).sqrt();
+    slows_below(points, |_| speed)
+}
+
+/// Range at which speed first drops below `threshold`, interpolated within
+/// the step.
+fn slows_below(
+    points: &[TrajectoryPoint],
+    threshold: impl Fn(&TrajectoryPoint) -> f64,
+) -> Option<f64> {
+    let margin = |point: &TrajectoryPoint| point.velocity.magnitude() - threshold(point);
+    points.windows(2).find_map(|pair| {
+        let (before, after) = (margin(&pair[0]), margin(&pair[1]));
+        if before < 0.0 || after >= 0.0 {
+            return None;
+        }
+        let t = before / (before - after);
+        Some(pair[0].position.x + (pair[1].position.x - pair[0].position.x) * t)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drag::BcBand;
+    use crate::wind::WindSample;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {expected} ± {tolerance}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn drag_force_matches_formula() {
+        // 0.5 * rho * v^2 / (bc * d^2) for a .308 at 850 m/s and bc 0.4
+        let drag = drag_force(850.0, 0.00762, 0.4, DragModel::Simple, 1.225, 340.0);
+        let expected = -0.5 * 1.225 * 850.0_f64.powi(2) / (0.4 * 0.00762_f64.powi(2));
+        assert_close(drag.x, expected, expected.abs() * 1e-12);
+        assert_close(drag.y, expected, expected.abs() * 1e-12);
+        assert_close(drag.z, expected, expected.abs() * 1e-12);
+    }
+
+    #[test]
+    fn vector_operators_work_component_wise() {
+        let a = Vector3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        let b = Vector3 {
+            x: 0.5,
+            y: -1.0,
+            z: 2.0,
+        };
+        assert_eq!(
+            a + b,
+            Vector3 {
+                x: 1.5,
+                y: 1.0,
+                z: 5.0
+            }
+        );
+        assert_eq!(a - b - a, b * -1.0);
+        let mut sum = a;
+        sum += b;
+        assert_eq!(sum, a + b);
+        assert_eq!(Vector2 { x: 3.0, y: 4.0 }.magnitude(), 5.0);
+        assert_eq!(
+            Vector2 { x: 1.0, y: 2.0 } * 2.0 - Vector2 { x: 1.0, y: 1.0 },
+            Vector2 { x: 1.0, y: 3.0 }
+        );
+    }
+
+    #[test]
+    fn cross_product_follows_the_right_hand_rule() {
+        let x = Vector3 {
+            x: 1.0,
+            ..Vector3::default()
+        };
+        let y = Vector3 {
+            y: 1.0,
+            ..Vector3::default()
+        };
+        assert_eq!(
+            x.cross(y),
+            Vector3 {
+                z: 1.0,
+                ..Vector3::default()
+            }
+        );
+        assert_eq!(y.cross(x), x.cross(y) * -1.0);
+    }
+
+    #[test]
+    fn acceleration_at_rest_is_just_gravity() {
+        let projectile = Projectile {
+            position: Vector3 {
+                x: 50.0,
+                y: 10.0,
+                z: 0.0,
+            },
+            velocity: Vector3::default(),
+        };
+        let params = ShotParams {
+            latitude: 45.0,
+            ..ShotParams::default()
+        };
+        assert_eq!(
+            acceleration(&projectile, &params),
+            Vector3 {
+                x: 0.0,
+                y: -GRAVITY,
+                z: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn stationary_projectile_only_feels_gravity() {
+        let mut projectile = Projectile::default();
+        update_velocity(&mut projectile, 0.01, &ShotParams::default());
+        assert_eq!(projectile.velocity.x, 0.0);
+        assert_eq!(projectile.velocity.z, 0.0);
+        assert_close(projectile.velocity.y, -GRAVITY * 0.01, 1e-12);
+    }
+
+    #[test]
+    fn wind_pushes_a_stationary_projectile_downwind() {
+        let mut projectile = Projectile::default();
+        let params = ShotParams {
+            wind: 5.0,
+            wind_direction: 90.0,
+            ..ShotParams::default()
+        };
+        update_velocity(&mut projectile, 0.01, &params);
+        assert!(projectile.velocity.z < 0.0);
+        assert_close(projectile.velocity.x, 0.0, 1e-12);
+    }
+
+    #[test]
+    fn crosswind_drifts_without_changing_range() {
+        let params = ShotParams {
+            elevation: 1.0,
+            ..ShotParams::default()
+        };
+        let windy = ShotParams {
+            wind: 5.0,
+            wind_direction: 90.0,
+            ..params
+        };
+        let calm = *simulate(&params).unwrap().last().unwrap();
+        let drifted = *simulate(&windy).unwrap().last().unwrap();
+
+        assert_close(drifted.position.x, calm.position.x, 0.5);
+        // Wind from the right pushes the bullet left.
+        assert!(drifted.position.z < -0.1, "{}", drifted.position.z);
+    }
+
+    #[test]
+    fn single_wind_sample_matches_constant_wind() {
+        let constant = ShotParams {
+            elevation: 1.0,
+            wind: 5.0,
+            wind_direction: 60.0,
+            ..ShotParams::default()
+        };
+        let sampled = ShotParams {
+            wind: 0.0,
+            wind_profile: WindProfile::from_slice(&[WindSample {
+                height: 0.0,
+                speed: 5.0,
+                direction: 60.0,
+            }]),
+            ..constant
+        };
+        assert_eq!(simulate(&constant).unwrap(), simulate(&sampled).unwrap());
+    }
+
+    #[test]
+    fn apex_wind_matters_for_arcs_but_not_flat_shots() {
+        let drift = |elevation, apex_speed| {
+            let params = ShotParams {
+                elevation,
+                wind_profile: WindProfile::from_slice(&[
+                    WindSample {
+                        height: 0.0,
+                        speed: 5.0,
+                        direction: 90.0,
+                    },
+                    WindSample {
+                        height: 500.0,
+                        speed: apex_speed,
+                        direction: 90.0,
+                    },
+                ]),
+                ..ShotParams::default()
+            };
+            simulate(&params).unwrap().last().unwrap().position.z
+        };
+
+        let (arc, arc_doubled) = (drift(30.0, 10.0), drift(30.0, 20.0));
+        assert!(
+            (arc_doubled - arc).abs() > 0.2 * arc.abs(),
+            "{arc} {arc_doubled}"
+        );
+
+        let (flat, flat_doubled) = (drift(0.5, 10.0), drift(0.5, 20.0));
+        assert!(
+            (flat_doubled - flat).abs() < 0.05 * flat.abs(),
+            "{flat} {flat_doubled}"
+        );
+    }
+
+    #[test]
+    fn headwind_shortens_range() {
+        let params = ShotParams {
+            elevation: 1.0,
+            ..ShotParams::default()
+        };
+        let range = |wind_direction| {
+            let params = ShotParams {
+                wind: 10.0,
+                wind_direction,
+                ..params
+            };
+            simulate(&params).unwrap().last().unwrap().position.x
+        };
+        assert!(range(0.0) < range(180.0));
+    }
+
+    #[test]
+    fn magnus_needs_a_crosswind() {
+        let params = ShotParams {
+            elevation: 1.0,
+            ..ShotParams::default()
+        };
+        let with_magnus = ShotParams {
+            magnus_enabled: true,
+            ..params
+        };
+        assert_eq!(simulate(&params), simulate(&with_magnus));
+        let velocity = Vector3 {
+            x: 800.0,
+            y: 10.0,
+            z: 0.0,
+        };
+        let calm = magnus_acceleration(
+            160_000.0,
+            velocity,
+            Vector3::default(),
+            TwistDirection::Right,
+        );
+        assert_eq!(calm, Vector3::default());
+
+        // Wind from the left lifts a right-hand twist, a little.
+        let height_at_800m = |params: &ShotParams| {
+            let points = simulate_to_range(params, 800.0);
+            point_at_range(&points, 800.0).unwrap().position.y
+        };
+        let windy = ShotParams {
+            wind: 4.5,
+            wind_direction: 270.0,
+            ..params
+        };
+        let lift = height_at_800m(&ShotParams {
+            magnus_enabled: true,
+            ..windy
+        }) - height_at_800m(&windy);
+        assert!(lift > 0.0 && lift < 0.05, "{lift}");
+    }
+
+    #[test]
+    fn head_and_tail_winds_move_the_landing_either_way() {
+        let params = ShotParams {
+            elevation: 1.0,
+            ..ShotParams::default()
+        };
+        let range = |wind, wind_direction| {
+            let params = ShotParams {
+                wind,
+                wind_direction,
+                ..params
+            };
+            landing_point(&simulate(&params).unwrap())
+                .unwrap()
+                .position
+                .x
+        };
+        let calm = range(0.0, 0.0);
+        assert!(range(10.0, 0.0) < calm);
+        assert!(range(10.0, 180.0) > calm);
+
+        let cost = |wind_direction| {
+            headwind_range_cost(&ShotParams {
+                wind: 10.0,
+                wind_direction,
+                ..params
+            })
+            .unwrap()
+        };
+        assert!(cost(0.0) > 0.0);
+        assert!(cost(180.0) < 0.0);
+        // A pure crosswind costs nothing downrange.
+        assert_close(cost(90.0), 0.0, 1e-9);
+        // Neither shot is cut short by the range cap.
+        let capped = headwind_range_cost(&ShotParams {
+            wind: 10.0,
+            max_range: 100.0,
+            ..params
+        });
+        assert_eq!(capped, Ok(cost(0.0)));
+        assert_close(headwind(10.0, 0.0), 10.0, 1e-12);
+        assert_close(crosswind(10.0, 270.0), 10.0, 1e-12);
+    }
+
+    #[test]
+    fn position_advances_by_velocity() {
+        let mut projectile = Projectile {
+            position: Vector3 {
+                x: 1.0,
+                y: 2.0,
+                z: 0.0,
+            },
+            velocity: Vector3 {
+                x: 100.0,
+                y: -10.0,
+                z: 5.0,
+            },
+        };
+        update_position(&mut projectile, 0.01);
+        assert_close(projectile.position.x, 2.0, 1e-12);
+        assert_close(projectile.position.y, 1.9, 1e-12);
+        assert_close(projectile.position.z, 0.05, 1e-12);
+    }
+
+    // A huge bc on a 1 m "caliber" makes drag negligible, so the shot should
+    // land close to the vacuum range.
+    fn low_drag_params(elevation: f64) -> ShotParams {
+        ShotParams {
+            elevation,
+            caliber: 1.0,
+            ballistic_coefficient: 1e9,
+            muzzle_velocity: 100.0,
+            ..ShotParams::default()
+        }
+    }
+
+    #[test]
+    fn simulate_returns_to_ground() {
+        let points = simulate(&low_drag_params(45.0)).unwrap();
+        let last = points.last().unwrap();
+        let vacuum_range = 100.0_f64.powi(2) / GRAVITY;
+
+        assert_eq!(points[0].position, Vector3::default());
+        assert!(last.position.y <= 0.0);
+        assert_close(last.position.x, vacuum_range, 2.0);
+        assert!(points.windows(2).all(|w| w[1].time > w[0].time));
+    }
+
+    #[test]
+    fn level_shot_terminates() {
+        let points = simulate(&low_drag_params(0.0)).unwrap();
+        assert_eq!(points.len(), 2);
+    }
+
+    #[test]
+    fn simulate_is_bounded_by_max_steps() {
+        let params = ShotParams {
+            elevation: 90.0,
+            muzzle_velocity: 1e9,
+            ..low_drag_params(90.0)
+        };
+        assert!(simulate(&params).unwrap().len() <= MAX_STEPS + 1);
+    }
+
+    #[test]
+    fn uncapped_long_flight_lands_at_the_finest_step() {
+        // Two minutes in the air is more steps than any fixed guard short of
+        // MAX_STEPS allows at this step size.
+        let params = ShotParams {
+            elevation: 45.0,
+            drag_enabled: false,
+            time_step: MIN_TIME_STEP,
+            ..ShotParams::default()
+        }
+        .uncapped();
+        let points = simulate(&params).unwrap();
+        assert!(!truncated(&points));
+        let landing = landing_point(&points).unwrap();
+        let v = params.muzzle_velocity;
+        assert_close(
+            landing.time,
+            2.0 * v * 45f64.to_radians().sin() / GRAVITY,
+            1e-3,
+        );
+        assert_close(landing.position.x, v * v / GRAVITY, 1.0);
+    }
+
+    #[test]
+    fn time_cap_holds_at_any_step() {
+        for time_step in [MIN_TIME_STEP, TIME_STEP, MAX_TIME_STEP] {
+            let params = ShotParams {
+                elevation: 89.9,
+                drag_enabled: false,
+                time_step,
+                max_flight_time: 150.0,
+                ..ShotParams::default()
+            };
+            let points = simulate(&params).unwrap();
+            assert_close(points.last().unwrap().time, 150.0, time_step * 1.001);
+        }
+    }
+
+    #[test]
+    fn near_vertical_shot_stops_at_the_time_cap() {
+        let params = ShotParams {
+            elevation: 89.9,
+            muzzle_velocity: 1000.0,
+            drag_enabled: false,
+            max_flight_time: 5.0,
+            ..ShotParams::default()
+        };
+        let points = simulate(&params).unwrap();
+        let last = points.last().unwrap();
+        // Summed steps land a hair either side of the cap.
+        assert_close(last.time, 5.0, params.time_step * 1.001);
+        assert!(last.position.y > 0.0);
+        assert!(truncated(&points));
+        assert!(!truncated(&simulate(&low_drag_params(10.0)).unwrap()));
+    }
+
+    #[test]
+    fn shot_stops_at_the_range_cap() {
+        let params = ShotParams {
+            elevation: 30.0,
+            max_range: 500.0,
+            ..ShotParams::default()
+        };
+        let points = simulate(&params).unwrap();
+        let last = points.last().unwrap().position.x;
+        assert!((500.0..510.0).contains(&last), "{last}");
+        assert!(truncated(&points));
+    }
+
+    #[test]
+    fn zero_caliber_does_not_poison_the_trajectory() {
+        let params = ShotParams {
+            elevation: 1.0,
+            caliber: 0.0,
+            drag_model: DragModel::Simple,
+            ..ShotParams::default()
+        };
+        assert_eq!(simulate(&params), Err(BallisticError::InvalidCaliber));
+        // The unchecked integration stays finite all the same.
+        let points = simulate_to_range(&params, 500.0);
+        assert!(points.iter().all(|point| {
+            point.position.x.is_finite()
+                && point.position.y.is_finite()
+                && point.velocity.x.is_finite()
+        }));
+    }
+
+    #[test]
+    fn g1_velocity_at_100m_matches_published_tables() {
+        // .30 caliber, G1 bc 0.4 at 850 m/s keeps roughly 775 m/s at 100 m.
+        let params = ShotParams {
+            elevation: 1.0,
+            ..ShotParams::default()
+        };
+        let points = simulate(&params).unwrap();
+        let at_100m = points.iter().find(|p| p.position.x >= 100.0).unwrap();
+        let speed = (at_100m.velocity.x.powi(2) + at_100m.velocity.y.powi(2)).sqrt();
+        assert_close(speed, 775.0, 10.0);
+    }
+
+    #[test]
+    fn g1_and_g7_drop_differ_for_same_bc() {
+        let drop = |drag_model| {
+            let points = simulate(&ShotParams {
+                elevation: 2.0,
+                drag_model,
+                ..ShotParams::default()
+            })
+            .unwrap();
+            points.last().unwrap().position.x
+        };
+        let g1 = drop(DragModel::G1);
+        let g7 = drop(DragModel::G7);
+        // The G7 reference is far slicker, so the same bc means less drag.
+        assert!(g7 - g1 > 50.0, "g1 {g1} g7 {g7}");
+    }
+
+    #[test]
+    fn banded_bc_drops_differently_from_the_average() {
+        let drop_at_800m = |params: ShotParams| {
+            let points = simulate_to_range(&params, 800.0);
+            point_at_range(&points, 800.0).unwrap().position.y
+        };
+        let bands = BcBands::from_slice(&[
+            BcBand {
+                velocity: 600.0,
+                ballistic_coefficient: 0.45,
+            },
+            BcBand {
+                velocity: 0.0,
+                ballistic_coefficient: 0.35,
+            },
+        ]);
+        let single = drop_at_800m(ShotParams::default());
+        let banded = drop_at_800m(ShotParams {
+            bc_bands: bands,
+            ..ShotParams::default()
+        });
+        assert!(
+            (single - banded).abs() > 0.05,
+            "single {single} banded {banded}"
+        );
+    }
+
+    #[test]
+    fn coriolis_lifts_eastward_fire_at_45_degrees() {
+        let omega = earth_rotation(45.0, 90.0);
+        let velocity = Vector3 {
+            x: 800.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let coriolis = coriolis_acceleration(omega, velocity);
+        let expected = 2.0 * EARTH_ANGULAR_VELOCITY * 45.0_f64.to_radians().cos() * 800.0;
+
+        // Eötvös: firing east makes the bullet slightly lighter.
+        assert_close(coriolis.y, expected, 1e-12);
+        // And the northern hemisphere deflects it to the right.
+        assert!(coriolis.z > 0.0);
+    }
+
+    #[test]
+    fn earth_rotation_tilts_with_latitude() {
+        let north = |latitude| earth_rotation(latitude, 0.0);
+        assert_close(north(0.0).x, EARTH_ANGULAR_VELOCITY, 1e-18);
+        assert_close(north(0.0).y, 0.0, 1e-18);
+        assert_close(north(90.0).y, EARTH_ANGULAR_VELOCITY, 1e-18);
+        assert_close(north(90.0).x, 0.0, 1e-18);
+        assert_close(north(-30.0).y, -EARTH_ANGULAR_VELOCITY / 2.0, 1e-18);
+        // However it's split, the rotation rate itself doesn't change.
+        for latitude in [-60.0, 12.5, 51.5] {
+            assert_close(
+                earth_rotation(latitude, 135.0).magnitude(),
+                EARTH_ANGULAR_VELOCITY,
+                1e-18,
+            );
+        }
+    }
+
+    #[test]
+    fn eotvos_lifts_eastward_fire_and_drops_westward() {
+        let lift = |azimuth| {
+            let params = ShotParams {
+                elevation: 1.0,
+                latitude: 45.0,
+                azimuth,
+                ..ShotParams::default()
+            };
+            eotvos_lift(&params, 1000.0).unwrap()
+        };
+        let (east, west) = (lift(90.0), lift(270.0));
+        assert!(east > 0.0 && east < 0.1, "{east}");
+        assert!(west < 0.0, "{west}");
+        assert_close(east, -west, 0.1 * east);
+        // Firing north, Earth's rotation mostly turns the shot sideways.
+        assert!(lift(0.0).abs() < east / 100.0, "{}", lift(0.0));
+    }
+
+    #[test]
+    fn coriolis_deflects_long_shot_laterally() {
+        let params = ShotParams {
+            elevation: 1.0,
+            latitude: 45.0,
+            azimuth: 0.0,
+            ..ShotParams::default()
+        };
+        let last = *simulate(&params).unwrap().last().unwrap();
+        assert!(last.position.z > 0.0);
+
+        let equator = ShotParams {
+            latitude: 0.0,
+            azimuth: 90.0,
+            ..params
+        };
+        assert_close(
+            simulate(&equator).unwrap().last().unwrap().position.z,
+            0.0,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn thin_air_flattens_trajectory() {
+        let params = ShotParams {
+            elevation: 1.0,
+            ..ShotParams::default()
+        };
+        let high = ShotParams {
+            altitude: 2000.0,
+            ..params
+        };
+        let range = |params: &ShotParams| simulate(params).unwrap().last().unwrap().position.x;
+        assert!(range(&high) > range(&params));
+    }
+
+    #[test]
+    fn point_at_range_interpolates_between_samples() {
+        let sample = |time: f64, x: f64, y: f64| TrajectoryPoint {
+            time,
+            position: Vector3 { x, y, z: 0.0 },
+            ..TrajectoryPoint::default()
+        };
+        let points = [sample(0.0, 0.0, 0.0), sample(1.0, 10.0, 2.0)];
+
+        let mid = point_at_range(&points, 2.5).unwrap();
+        assert_close(mid.time, 0.25, 1e-12);
+        assert_close(mid.position.y, 0.5, 1e-12);
+        assert!(point_at_range(&points, 11.0).is_none());
+    }
+
+    #[test]
+    fn ground_impact_interpolates_the_last_step() {
+        let before = TrajectoryPoint {
+            time: 2.0,
+            position: Vector3 {
+                x: 100.0,
+                y: 0.3,
+                z: 1.0,
+            },
+            velocity: Vector3 {
+                x: 50.0,
+                y: -40.0,
+                z: 0.0,
+            },
+            ..TrajectoryPoint::default()
+        };
+        let after = TrajectoryPoint {
+            time: 2.01,
+            position: Vector3 {
+                x: 100.5,
+                y: -0.1,
+                z: 1.0,
+            },
+            velocity: Vector3 {
+                x: 50.0,
+                y: -40.1,
+                z: 0.0,
+            },
+            ..TrajectoryPoint::default()
+        };
+        let impact = ground_impact(&before, &after);
+        assert_close(impact.time, 2.0075, 1e-12);
+        assert_close(impact.position.x, 100.375, 1e-12);
+        assert_eq!(impact.position.y, 0.0);
+        assert_close(impact.velocity.y, -40.075, 1e-12);
+    }
+
+    #[test]
+    fn steeper_launch_falls_more_steeply() {
+        let angle_of_fall = |elevation| {
+            let params = ShotParams {
+                elevation,
+                ..ShotParams::default()
+            };
+            let points = simulate(&params).unwrap();
+            let landing = ground_impact(&points[points.len() - 2], &points[points.len() - 1]);
+            impact(&landing, params.mass).angle_of_fall
+        };
+        let low = angle_of_fall(2.0);
+        let high = angle_of_fall(10.0);
+        assert!(low > 2.0, "{low}");
+        assert!(high > low, "low {low} high {high}");
+    }
+
+    #[test]
+    fn flat_fire_velocity_only_decreases_with_drag() {
+        let points = simulate_to_range(
+            &ShotParams {
+                elevation: 1.0,
+                ..ShotParams::default()
+            },
+            1000.0,
+        );
+        assert!(points.len() > 2);
+        assert!(points
+            .windows(2)
+            .all(|pair| pair[1].velocity.magnitude() < pair[0].velocity.magnitude()));
+    }
+
+    #[test]
+    fn finds_where_the_shot_goes_subsonic() {
+        let params = ShotParams {
+            elevation: 1.0,
+            ..ShotParams::default()
+        };
+        let points = simulate_to_range(&params, 3000.0);
+        let range = range_at_mach(&points, &params, 1.0).unwrap();
+        let point = point_at_range(&points, range).unwrap();
+        let mach = point.velocity.magnitude() / params.speed_of_sound_at(point.position.y);
+        assert_close(mach, 1.0, 1e-3);
+        assert_eq!(range_at_mach(&points, &params, 0.01), None);
+    }
+
+    #[test]
+    fn higher_bc_stays_supersonic_farther() {
+        let range = |ballistic_coefficient| {
+            let params = ShotParams {
+                ballistic_coefficient,
+                ..ShotParams::default()
+            };
+            supersonic_range(&simulate_to_range(&params, 3000.0), &params).unwrap()
+        };
+        let (low, high) = (range(0.25), range(0.6));
+        assert!(high > low + 200.0, "{low} vs {high}");
+
+        let subsonic = ShotParams {
+            muzzle_velocity: 300.0,
+            ..ShotParams::default()
+        };
+        let points = simulate_to_range(&subsonic, 500.0);
+        assert_eq!(supersonic_range(&points, &subsonic), None);
+    }
+
+    #[test]
+    fn only_supersonic_loads_go_transonic() {
+        let range = |muzzle_velocity| {
+            let points = simulate_to_range(
+                &ShotParams {
+                    elevation: 1.0,
+                    muzzle_velocity,
+                    ..ShotParams::default()
+                },
+                2000.0,
+            );
+            transonic_range(&points, STANDARD_TEMPERATURE)
+        };
+        assert_eq!(range(350.0), None);
+        let rifle = range(850.0).unwrap();
+        assert!(rifle > 300.0 && rifle < 2000.0, "{rifle}");
+    }
+
+    #[test]
+    fn warmer_powder_shoots_faster() {
+        let params = ShotParams {
+            velocity_temp_sensitivity: 1.0,
+            temperature: STANDARD_TEMPERATURE + 20.0,
+            ..ShotParams::default()
+        };
+        assert_close(
+            params.effective_muzzle_velocity(),
+            params.muzzle_velocity + 20.0,
+            1e-9,
+        );
+        assert_eq!(
+            ShotParams::default().effective_muzzle_velocity(),
+            ShotParams::default().muzzle_velocity
+        );
+    }
+
+    #[test]
+    fn closest_approach_falls_between_samples() {
+        let sample = |time, x| TrajectoryPoint {
+            time,
+            position: Vector3 { x, y: 1.0, z: 0.0 },
+            ..TrajectoryPoint::default()
+        };
+        let (a, b) = (sample(0.0, 0.0), sample(1.0, 10.0));
+        let target = Vector3 {
+            x: 4.0,
+            y: 1.5,
+            z: 0.0,
+        };
+        let (point, distance) = closest_approach(&a, &b, target);
+        assert_close(point.position.x, 4.0, 1e-12);
+        assert_close(point.time, 0.4, 1e-12);
+        assert_close(distance, 0.5, 1e-12);
+
+        // Beyond the end of the step the end itself is closest.
+        let behind = Vector3 { x: -3.0, ..target };
+        assert_eq!(closest_approach(&a, &b, behind).0, a);
+    }
+
+    #[test]
+    fn muzzle_energy_of_a_168gr_308() {
+        // 168 gr at 2650 fps is quoted as about 2620 ft-lb.
+        let energy = kinetic_energy(168.0 * 6.479891e-5, 2650.0 * 0.3048);
+        assert_close(crate::units::foot_pounds(energy), 2619.6, 1.0);
+    }
+
+    #[test]
+    fn energy_threshold_is_where_the_speed_gives_it() {
+        let params = ShotParams::default();
+        let points = simulate_to_range(&params, 1000.0);
+        let muzzle = kinetic_energy(params.mass, params.muzzle_velocity);
+        let range = range_below_energy(&points, params.mass, muzzle / 2.0).unwrap();
+        let point = point_at_range(&points, range).unwrap();
+        assert_close(
+            kinetic_energy(params.mass, point.velocity.magnitude()),
+            muzzle / 2.0,
+            5.0,
+        );
+        // Starting out below the threshold isn't dropping below it.
+        assert_eq!(range_below_energy(&points, params.mass, muzzle * 2.0), None);
+    }
+
+    #[test]
+    fn impact_energy_uses_full_speed() {
+        let point = TrajectoryPoint {
+            velocity: Vector3 {
+                x: 300.0,
+                y: -400.0,
+                z: 0.0,
+            },
+            ..TrajectoryPoint::default()
+        };
+        let impact = impact(&point, 0.01);
+        assert_close(impact.velocity, 500.0, 1e-9);
+        assert_close(impact.energy, 1250.0, 1e-9);
+        assert_close(impact.angle_of_fall, 53.130102, 1e-6);
+    }
+
+    #[test]
+    fn time_of_flight_matches_vacuum_formula() {
+        let params = ShotParams {
+            elevation: 30.0,
+            muzzle_velocity: 100.0,
+            drag_enabled: false,
+            ..ShotParams::default()
+        };
+        let landing = landing_point(&simulate(&params).unwrap()).unwrap();
+        assert_close(landing.time, 2.0 * 50.0 / GRAVITY, 1e-4);
+    }
+
+    #[test]
+    fn impact_trajectory_ends_on_the_ground() {
+        let params = ShotParams {
+            elevation: 5.0,
+            ..ShotParams::default()
+        };
+        let full = simulate(&params).unwrap();
+        let clipped = simulate_to_impact(&params).unwrap();
+        assert_eq!(full.len(), clipped.len());
+        assert_eq!(clipped.last().unwrap().position.y, 0.0);
+        assert_eq!(full[full.len() - 2], clipped[clipped.len() - 2]);
+    }
+
+    #[test]
+    fn drag_makes_the_arc_asymmetric() {
+        let params = ShotParams {
+            elevation: 20.0,
+            ..ShotParams::default()
+        };
+        let points = simulate(&params).unwrap();
+        let apex = max_ordinate(&points).unwrap();
+        let landing = landing_point(&points).unwrap();
+        assert!(apex.position.y > 0.0);
+        // The bullet climbs quickly while it's fast and falls slowly once
+        // drag has bled off its speed: the apex comes before half the flight
+        // time, but the steep descent puts it past half the range.
+        assert!(apex.time < landing.time / 2.0);
+        assert!(apex.position.x > landing.position.x / 2.0);
+    }
+
+    #[test]
+    fn sight_line_sits_above_the_bore() {
+        let point = TrajectoryPoint {
+            position: Vector3 {
+                x: 100.0,
+                y: -0.05,
+                z: 0.0,
+            },
+            ..TrajectoryPoint::default()
+        };
+        assert_close(height_above_sight_line(&point, 0.0), -0.05, 1e-12);
+        assert_close(height_above_sight_line(&point, 0.05), -0.10, 1e-12);
+    }
+
+    #[test]
+    fn frames_differ_by_the_bore_and_sight_lines() {
+        let params = ShotParams {
+            elevation: 0.1,
+            ..ShotParams::default()
+        };
+        let points = simulate_to_range(&params, 600.0);
+        let sight_height = 0.05;
+        for range in [100.0, 300.0, 600.0] {
+            let point = point_at_range(&points, range).unwrap();
+            let height = |frame| height_in_frame(&point, frame, params.elevation, sight_height);
+            let bore_line = range * 0.1_f64.to_radians().tan();
+            assert_close(
+                height(Frame::LineOfSight) - height(Frame::Bore),
+                bore_line - sight_height,
+                1e-12,
+            );
+            assert_eq!(
+                height(Frame::LineOfSight),
+                height_above_sight_line(&point, sight_height)
+            );
+            // The bullet only falls away from where the bore points.
+            assert!(height(Frame::Bore) < 0.0);
+        }
+    }
+
+    #[test]
+    fn rk4_tracks_the_vacuum_parabola_more_closely_than_euler() {
+        let angle = 30.0_f64;
+        let params = ShotParams {
+            elevation: angle,
+            muzzle_velocity: 100.0,
+            ballistic_coefficient: f64::INFINITY,
+            ..ShotParams::default()
+        };
+        let time = 5.0;
+        let v0 = launch_velocity(params.muzzle_velocity, angle);
+        let expected_y = v0.y * time - 0.5 * GRAVITY * time.powi(2);
+
+        let error = |integrator| {
+            let params = ShotParams {
+                integrator,
+                ..params
+            };
+            let mut projectile = Projectile {
+                position: Vector3::default(),
+                velocity: v0,
+            };
+            for _ in 0..500 {
+                step(&mut projectile, TIME_STEP, &params);
+            }
+            assert_close(projectile.position.x, v0.x * time, 1e-9);
+            (projectile.position.y - expected_y).abs()
+        };
+
+        let euler = error(Integrator::Euler);
+        let rk4 = error(Integrator::Rk4);
+        assert!(euler > 0.1, "euler {euler}");
+        // What's left for RK4 is Coriolis, which the parabola ignores.
+        assert!(rk4 < 1e-3, "rk4 {rk4}");
+    }
+
+    #[test]
+    fn vacuum_range_matches_closed_form() {
+        let params = ShotParams {
+            elevation: 30.0,
+            muzzle_velocity: 100.0,
+            wind: 10.0,
+            latitude: 45.0,
+            drag_enabled: false,
+            ..ShotParams::default()
+        };
+        let points = simulate(&params).unwrap();
+        let impact = ground_impact(&points[points.len() - 2], &points[points.len() - 1]);
+        let expected = 100.0_f64.powi(2) * 60.0_f64.to_radians().sin() / GRAVITY;
+
+        assert_close(impact.position.x, expected, 1e-3);
+        assert_close(impact.position.z, 0.0, 1e-12);
+    }
+
+    #[test]
+    fn halving_the_time_step_halves_the_euler_error() {
+        let error = |time_step| {
+            let params = ShotParams {
+                elevation: 30.0,
+                muzzle_velocity: 100.0,
+                integrator: Integrator::Euler,
+                drag_enabled: false,
+                time_step,
+                ..ShotParams::default()
+            };
+            let landing = landing_point(&simulate(&params).unwrap()).unwrap();
+            let expected = 100.0_f64.powi(2) * 60.0_f64.to_radians().sin() / GRAVITY;
+            (landing.position.x - expected).abs()
+        };
+        let (coarse, fine) = (error(0.02), error(0.01));
+        assert!(fine < coarse * 0.6, "{coarse} vs {fine}");
+    }
+
+    #[test]
+    fn coarse_steps_lose_too_much_speed_to_drag() {
+        let loss = |time_step| {
+            drag_loss_per_step(&ShotParams {
+                time_step,
+                ..ShotParams::default()
+            })
+        };
+        assert!(loss(TIME_STEP) < MAX_DRAG_LOSS_PER_STEP);
+        assert!(loss(MAX_TIME_STEP) > MAX_DRAG_LOSS_PER_STEP);
+        assert_close(loss(0.02), 2.0 * loss(0.01), 1e-12);
+    }
+
+    #[test]
+    fn integrator_parses_its_label() {
+        for integrator in Integrator::ALL {
+            assert_eq!(integrator.label().parse(), Ok(integrator));
+        }
+    }
+
+    #[test]
+    fn simulate_to_range_continues_below_the_muzzle() {
+        let params = ShotParams::default();
+        let points = simulate_to_range(&params, 500.0);
+        let last = points.last().unwrap();
+        assert!(last.position.x >= 500.0);
+        assert!(last.position.y < 0.0);
+    }
+}
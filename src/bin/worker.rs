@@ -0,0 +1,11 @@
+// The worker only exists in the browser; natively there's nothing to run.
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use enigma::worker::TrajectoryWorker;
+    use gloo_worker::Registrable;
+
+    TrajectoryWorker::registrar().register();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {}
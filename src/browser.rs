@@ -0,0 +1,133 @@
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, HtmlInputElement, Storage, Url};
+
+/// Saves `contents` as a file through a temporary object URL.
+pub fn download(filename: &str, mime: &str, contents: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let result = click_link(&url, filename);
+    // Revoke even if the click failed so the blob isn't kept alive.
+    Url::revoke_object_url(&url)?;
+    result
+}
+
+fn click_link(url: &str, filename: &str) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let anchor: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(url);
+    anchor.set_download(filename);
+    anchor.click();
+    Ok(())
+}
+
+/// Reads the first file picked in a file input as text.
+pub async fn read_text(input: &HtmlInputElement) -> Result<String, JsValue> {
+    let file = input
+        .files()
+        .and_then(|files| files.get(0))
+        .ok_or_else(|| JsValue::from_str("no file selected"))?;
+    let text = JsFuture::from(file.text()).await?;
+    text.as_string()
+        .ok_or_else(|| JsValue::from_str("file is not text"))
+}
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// The value stored under `key`, if storage is available and has one.
+pub fn load(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+/// Stores `value` under `key`. Quietly does nothing where storage is
+/// disabled, since remembering inputs is only a convenience.
+pub fn store(key: &str, value: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(key, value);
+    }
+}
+
+pub fn forget(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
+
+/// The current page's query string, including its `?`, if it has one.
+pub fn query() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    (!search.is_empty()).then_some(search)
+}
+
+/// This page's address with its query string replaced by `query`.
+pub fn url_with_query(query: &str) -> Result<String, JsValue> {
+    let location = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("no window"))?
+        .location();
+    Ok(format!(
+        "{}{}?{}",
+        location.origin()?,
+        location.pathname()?,
+        query
+    ))
+}
+
+/// Opens the browser's print dialog for the page as it stands.
+pub fn print() -> Result<(), JsValue> {
+    web_sys::window()
+        .ok_or_else(|| JsValue::from_str("no window"))?
+        .print()
+}
+
+pub async fn copy_text(text: &str) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    JsFuture::from(window.navigator().clipboard().write_text(text)).await?;
+    Ok(())
+}
+
+/// Selects everything inside `element`, as if dragged over, so it can be
+/// copied by hand when the clipboard can't be written to.
+pub fn select_contents(element: &web_sys::Element) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    let document = window
+        .document()
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let selection = window
+        .get_selection()?
+        .ok_or_else(|| JsValue::from_str("no selection"))?;
+    let range = document.create_range()?;
+    range.select_node_contents(element)?;
+    selection.remove_all_ranges()?;
+    selection.add_range(&range)
+}
+
+/// The device's latitude in degrees, positive north. Fails if the browser has
+/// no location to give or the user won't share it.
+pub async fn current_latitude() -> Result<f64, JsValue> {
+    let geolocation = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("no window"))?
+        .navigator()
+        .geolocation()?;
+    let position = js_sys::Promise::new(&mut |resolve, reject| {
+        if let Err(err) =
+            geolocation.get_current_position_with_error_callback(&resolve, Some(&reject))
+        {
+            let _ = reject.call1(&JsValue::NULL, &err);
+        }
+    });
+    // The position bindings are behind web-sys's unstable APIs, so the
+    // coordinates are read off the plain object.
+    let position = JsFuture::from(position).await?;
+    let coords = js_sys::Reflect::get(&position, &JsValue::from_str("coords"))?;
+    js_sys::Reflect::get(&coords, &JsValue::from_str("latitude"))?
+        .as_f64()
+        .ok_or_else(|| JsValue::from_str("no latitude in the position"))
+}
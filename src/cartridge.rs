@@ -0,0 +1,85 @@
+use crate::drag::DragModel;
+
+/// Published load data for a factory cartridge, in SI units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CartridgePreset {
+    pub name: &'static str,
+    /// Bullet diameter in meters.
+    pub caliber: f64,
+    /// Bullet mass in kilograms.
+    pub mass: f64,
+    pub ballistic_coefficient: f64,
+    pub drag_model: DragModel,
+    /// Typical muzzle velocity in m/s.
+    pub muzzle_velocity: f64,
+}
+
+pub const CARTRIDGES: &[CartridgePreset] = &[
+    CartridgePreset {
+        name: ".308 Win 168gr",
+        caliber: 0.00782,
+        mass: 0.010886,
+        ballistic_coefficient: 0.462,
+        drag_model: DragModel::G1,
+        muzzle_velocity: 792.0,
+    },
+    CartridgePreset {
+        name: "6.5 Creedmoor 140gr",
+        caliber: 0.00671,
+        mass: 0.009072,
+        ballistic_coefficient: 0.315,
+        drag_model: DragModel::G7,
+        muzzle_velocity: 826.0,
+    },
+    CartridgePreset {
+        name: "5.56 NATO 55gr",
+        caliber: 0.00570,
+        mass: 0.003564,
+        ballistic_coefficient: 0.243,
+        drag_model: DragModel::G1,
+        muzzle_velocity: 990.0,
+    },
+    CartridgePreset {
+        name: ".300 Win Mag 190gr",
+        caliber: 0.00782,
+        mass: 0.012312,
+        ballistic_coefficient: 0.533,
+        drag_model: DragModel::G1,
+        muzzle_velocity: 884.0,
+    },
+    CartridgePreset {
+        name: ".338 Lapua 250gr",
+        caliber: 0.00861,
+        mass: 0.016200,
+        ballistic_coefficient: 0.322,
+        drag_model: DragModel::G7,
+        muzzle_velocity: 905.0,
+    },
+];
+
+/// Looks a preset up by its display name.
+pub fn find(name: &str) -> Option<&'static CartridgePreset> {
+    CARTRIDGES.iter().find(|preset| preset.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_preset_has_positive_bc_and_caliber() {
+        for preset in CARTRIDGES {
+            assert!(preset.ballistic_coefficient > 0.0, "{}", preset.name);
+            assert!(preset.caliber > 0.0, "{}", preset.name);
+        }
+    }
+
+    #[test]
+    fn finds_presets_by_name() {
+        assert_eq!(
+            find("6.5 Creedmoor 140gr").unwrap().drag_model,
+            DragModel::G7
+        );
+        assert!(find("nonexistent").is_none());
+    }
+}
@@ -0,0 +1,37 @@
+use enigma::ballistics::simulate_to_range;
+use enigma::error::BallisticError;
+use enigma::profile::ShotProfile;
+use enigma::table::{range_table, to_csv};
+use enigma::units::UnitSystem;
+use enigma::validation;
+
+use crate::input::parse_value;
+
+const USAGE: &str = "usage: ballistic-calc <profile.json> [step meters] [max meters]";
+const DEFAULT_STEP: f64 = 100.0;
+const DEFAULT_MAX: f64 = 1000.0;
+
+/// Reads the profile named in `args` and renders its range table as CSV.
+pub fn run(args: &[String]) -> Result<String, String> {
+    let (path, rest) = args.split_first().ok_or(USAGE)?;
+    let distance = |index: usize, default| match rest.get(index) {
+        Some(arg) => parse_value(arg).ok_or_else(|| format!("not a distance: {}\n{}", arg, USAGE)),
+        None => Ok(default),
+    };
+    let step = distance(0, DEFAULT_STEP)?;
+    let max = distance(1, DEFAULT_MAX)?;
+
+    let json =
+        std::fs::read_to_string(path).map_err(|err| format!("could not read {}: {}", path, err))?;
+    let profile = ShotProfile::from_json(&json).map_err(|err| format!("{}: {}", path, err))?;
+
+    validation::check(&profile.shot).map_err(|err| format!("{}: {}", path, err))?;
+    // Energies would all come out as zero without a mass.
+    if profile.shot.mass <= 0.0 || profile.shot.mass.is_nan() {
+        return Err(format!("{}: {}", path, BallisticError::InvalidMass));
+    }
+
+    let points = simulate_to_range(&profile.shot, max);
+    let rows = range_table(&points, step, max, profile.shot.mass, profile.sight_height);
+    Ok(to_csv(&rows, UnitSystem::Metric))
+}
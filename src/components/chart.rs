@@ -0,0 +1,144 @@
+use yew::prelude::*;
+
+use enigma::plot::{ticks, Bounds, Viewport};
+
+const VIEWPORT: Viewport = Viewport {
+    width: 640.0,
+    height: 320.0,
+    margin: 48.0,
+};
+const TICK_COUNT: usize = 6;
+
+#[derive(Clone, PartialEq)]
+pub struct Series {
+    pub points: Vec<(f64, f64)>,
+    pub color: &'static str,
+    /// Name shown in the legend; charts with no named series have none.
+    pub label: Option<&'static str>,
+}
+
+/// A labelled vertical line at `x`, e.g. where something changes regime.
+#[derive(Clone, PartialEq)]
+pub struct Marker {
+    pub x: f64,
+    pub label: String,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LineChartProps {
+    pub series: Vec<Series>,
+    pub x_label: String,
+    pub y_label: String,
+    #[prop_or_default]
+    pub markers: Vec<Marker>,
+    /// Draw each point as a dot instead of joining them up.
+    #[prop_or_default]
+    pub scatter: bool,
+}
+
+/// An auto-scaled SVG line chart with labelled axis ticks.
+#[function_component]
+pub fn LineChart(props: &LineChartProps) -> Html {
+    let all_points: Vec<(f64, f64)> = props
+        .series
+        .iter()
+        .flat_map(|series| series.points.iter().copied())
+        .collect();
+    let Some(bounds) = Bounds::of(&all_points) else {
+        return html! {};
+    };
+    let screen = |point| VIEWPORT.to_screen(&bounds, point);
+
+    let (left, bottom) = screen((bounds.min_x, bounds.min_y));
+    let (right, top) = screen((bounds.max_x, bounds.max_y));
+
+    let x_ticks = ticks(bounds.min_x, bounds.max_x, TICK_COUNT).into_iter().map(|x| {
+        let (sx, _) = screen((x, bounds.min_y));
+        html! {
+            <g>
+                <line x1={sx.to_string()} y1={bottom.to_string()} x2={sx.to_string()} y2={(bottom + 4.0).to_string()} stroke="black" />
+                <text x={sx.to_string()} y={(bottom + 16.0).to_string()} font-size="10" text-anchor="middle">{format_tick(x)}</text>
+            </g>
+        }
+    });
+    let y_ticks = ticks(bounds.min_y, bounds.max_y, TICK_COUNT).into_iter().map(|y| {
+        let (_, sy) = screen((bounds.min_x, y));
+        html! {
+            <g>
+                <line x1={(left - 4.0).to_string()} y1={sy.to_string()} x2={left.to_string()} y2={sy.to_string()} stroke="black" />
+                <text x={(left - 6.0).to_string()} y={(sy + 3.0).to_string()} font-size="10" text-anchor="end">{format_tick(y)}</text>
+            </g>
+        }
+    });
+    let lines = props.series.iter().map(|series| {
+        if props.scatter {
+            return html! {
+                { for series.points.iter().map(|&point| {
+                    let (x, y) = screen(point);
+                    html! { <circle cx={format!("{:.1}", x)} cy={format!("{:.1}", y)} r="3" fill={series.color} /> }
+                }) }
+            };
+        }
+        let points = series
+            .points
+            .iter()
+            .map(|&point| {
+                let (x, y) = screen(point);
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        html! { <polyline points={points} fill="none" stroke={series.color} stroke-width="1.5" /> }
+    });
+
+    let markers = props
+        .markers
+        .iter()
+        .filter(|marker| (bounds.min_x..=bounds.max_x).contains(&marker.x))
+        .map(|marker| {
+            let (x, _) = screen((marker.x, bounds.min_y));
+            html! {
+                <g>
+                    <line x1={x.to_string()} y1={bottom.to_string()} x2={x.to_string()} y2={top.to_string()} stroke="crimson" stroke-dasharray="4 3" />
+                    <text x={(x + 4.0).to_string()} y={(top + 12.0).to_string()} font-size="10" fill="crimson">{&marker.label}</text>
+                </g>
+            }
+        });
+
+    let legend = props
+        .series
+        .iter()
+        .filter_map(|series| Some((series.label?, series.color)))
+        .enumerate()
+        .map(|(index, (label, color))| {
+            let y = top + 12.0 + index as f64 * 14.0;
+            html! {
+                <g>
+                    <rect x={(right - 80.0).to_string()} y={(y - 8.0).to_string()} width="10" height="10" fill={color} />
+                    <text x={(right - 66.0).to_string()} y={(y + 1.0).to_string()} font-size="10">{label}</text>
+                </g>
+            }
+        });
+
+    html! {
+        <svg width={VIEWPORT.width.to_string()} height={VIEWPORT.height.to_string()}>
+            <line x1={left.to_string()} y1={bottom.to_string()} x2={right.to_string()} y2={bottom.to_string()} stroke="black" />
+            <line x1={left.to_string()} y1={bottom.to_string()} x2={left.to_string()} y2={top.to_string()} stroke="black" />
+            { for x_ticks }
+            { for y_ticks }
+            { for lines }
+            { for markers }
+            { for legend }
+            <text x={((left + right) / 2.0).to_string()} y={(VIEWPORT.height - 8.0).to_string()} font-size="12" text-anchor="middle">{&props.x_label}</text>
+            <text x="12" y={((top + bottom) / 2.0).to_string()} font-size="12" text-anchor="middle" transform={format!("rotate(-90 12 {})", (top + bottom) / 2.0)}>{&props.y_label}</text>
+        </svg>
+    }
+}
+
+fn format_tick(value: f64) -> String {
+    if value.abs() >= 10.0 || value == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
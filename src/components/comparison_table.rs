@@ -0,0 +1,59 @@
+use yew::prelude::*;
+
+use enigma::table::{ComparisonRow, DROP_SIGN};
+use enigma::units::{Quantity, UnitSystem};
+
+#[derive(Properties, PartialEq)]
+pub struct ComparisonTableProps {
+    pub rows: Vec<ComparisonRow>,
+    pub units: UnitSystem,
+}
+
+/// Load A and load B side by side, with B's difference from A after each pair.
+#[function_component]
+pub fn ComparisonTable(props: &ComparisonTableProps) -> Html {
+    let units = props.units;
+    let header = |name: &str, quantity| format!("{} ({})", name, units.unit(quantity));
+    let drop_header =
+        |name: &str| format!("{} ({}; {})", name, units.unit(Quantity::Length), DROP_SIGN);
+    let length = |value| format!("{:.1}", units.from_si(Quantity::Length, value));
+    let velocity = |value| format!("{:.0}", units.from_si(Quantity::Velocity, value));
+
+    html! {
+        <table>
+            <thead>
+                <tr>
+                    <th>{header("Range", Quantity::Distance)}</th>
+                    <th>{drop_header("Drop A")}</th>
+                    <th>{drop_header("Drop B")}</th>
+                    <th>{drop_header("Δ Drop")}</th>
+                    <th>{header("Velocity A", Quantity::Velocity)}</th>
+                    <th>{header("Velocity B", Quantity::Velocity)}</th>
+                    <th>{header("Δ Velocity", Quantity::Velocity)}</th>
+                    <th>{"Energy A (J)"}</th>
+                    <th>{"Energy B (J)"}</th>
+                    <th>{"Δ Energy (J)"}</th>
+                </tr>
+            </thead>
+            <tbody>
+                { for props.rows.iter().map(|row| {
+                    let difference = row.difference();
+                    html! {
+                        <tr>
+                            <td>{format!("{:.0}", units.from_si(Quantity::Distance, row.a.range))}</td>
+                            <td>{length(row.a.height())}</td>
+                            <td>{length(row.b.height())}</td>
+                            <td>{length(difference.height())}</td>
+                            <td>{velocity(row.a.velocity)}</td>
+                            <td>{velocity(row.b.velocity)}</td>
+                            <td>{velocity(difference.velocity)}</td>
+                            <td>{format!("{:.0}", row.a.energy)}</td>
+                            <td>{format!("{:.0}", row.b.energy)}</td>
+                            <td>{format!("{:.0}", difference.energy)}</td>
+                        </tr>
+                    }
+                }) }
+            </tbody>
+        </table>
+    }
+}
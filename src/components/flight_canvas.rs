@@ -0,0 +1,99 @@
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use yew::prelude::*;
+
+use enigma::plot::{trace, Bounds, Viewport};
+
+const VIEWPORT: Viewport = Viewport {
+    width: 640.0,
+    height: 240.0,
+    margin: 12.0,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct FlightCanvasProps {
+    /// Downrange and vertical position of every point of the flight.
+    pub path: Rc<Vec<(f64, f64)>>,
+    /// How many points of `path` have been flown so far.
+    pub flown: usize,
+    /// Where the projectile is drawn, usually between two points of `path`.
+    pub head: (f64, f64),
+    /// Centre and radius of a target to draw, in the same units as `path`.
+    #[prop_or_default]
+    pub target: Option<((f64, f64), f64)>,
+}
+
+/// The projectile and the trail behind it, redrawn on every change.
+#[function_component]
+pub fn FlightCanvas(props: &FlightCanvasProps) -> Html {
+    let canvas_ref = use_node_ref();
+
+    {
+        let canvas_ref = canvas_ref.clone();
+        // Fit the target in too, so it stays visible when the shot falls short.
+        let extents: Vec<(f64, f64)> = props
+            .path
+            .iter()
+            .copied()
+            .chain(props.target.map(|(centre, _)| centre))
+            .collect();
+        let bounds = Bounds::of(&extents);
+        let points = bounds.map_or_else(Vec::new, |bounds| {
+            trace(&VIEWPORT, &bounds, &props.path, props.flown, props.head)
+        });
+        let target = bounds.zip(props.target).map(|(bounds, (centre, radius))| {
+            let (x, y) = VIEWPORT.to_screen(&bounds, centre);
+            let (_, edge) = VIEWPORT.to_screen(&bounds, (centre.0, centre.1 + radius));
+            (x, y, (y - edge).abs().max(2.0))
+        });
+        use_effect(move || {
+            if let Some(context) = context(&canvas_ref) {
+                draw(&context, &points, target);
+            }
+        });
+    }
+
+    html! {
+        <canvas ref={canvas_ref} width={VIEWPORT.width.to_string()} height={VIEWPORT.height.to_string()} />
+    }
+}
+
+fn context(canvas_ref: &NodeRef) -> Option<CanvasRenderingContext2d> {
+    let canvas = canvas_ref.cast::<HtmlCanvasElement>()?;
+    canvas.get_context("2d").ok()??.dyn_into().ok()
+}
+
+fn draw(
+    context: &CanvasRenderingContext2d,
+    points: &[(f64, f64)],
+    target: Option<(f64, f64, f64)>,
+) {
+    context.clear_rect(0.0, 0.0, VIEWPORT.width, VIEWPORT.height);
+    if let Some((x, y, radius)) = target {
+        context.set_stroke_style_str("darkgreen");
+        context.begin_path();
+        let _ = context.arc(x, y, radius, 0.0, std::f64::consts::TAU);
+        context.stroke();
+    }
+    let Some(&(head_x, head_y)) = points.last() else {
+        return;
+    };
+
+    context.set_stroke_style_str("steelblue");
+    context.begin_path();
+    for (index, &(x, y)) in points.iter().enumerate() {
+        if index == 0 {
+            context.move_to(x, y);
+        } else {
+            context.line_to(x, y);
+        }
+    }
+    context.stroke();
+
+    context.set_fill_style_str("crimson");
+    context.begin_path();
+    let _ = context.arc(head_x, head_y, 4.0, 0.0, std::f64::consts::TAU);
+    context.fill();
+}
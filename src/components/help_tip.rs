@@ -0,0 +1,53 @@
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct HelpTipProps {
+    /// What the input is, read out before the explanation.
+    pub label: &'static str,
+    pub body: &'static str,
+}
+
+/// A `?` beside an input that explains it on hover or keyboard focus.
+#[function_component]
+pub fn HelpTip(props: &HelpTipProps) -> Html {
+    html! {
+        <span class="help-tip" tabindex="0" aria-label={format!("About {}", props.label.to_lowercase())}>
+            {"?"}
+            <span class="help-body" role="tooltip">
+                <strong>{props.label}</strong>{": "}{props.body}
+            </span>
+        </span>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    use super::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    async fn renders_the_label_and_body() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let root = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&root).unwrap();
+        let help = enigma::help::TWIST_RATE;
+        yew::Renderer::<HelpTip>::with_root_and_props(
+            root.clone(),
+            HelpTipProps {
+                label: help.label,
+                body: help.body,
+            },
+        )
+        .render();
+        yew::platform::time::sleep(Duration::ZERO).await;
+
+        let text = root.text_content().unwrap();
+        assert!(text.starts_with('?'), "{text}");
+        assert!(text.contains(help.body), "{text}");
+    }
+}
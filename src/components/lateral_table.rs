@@ -0,0 +1,44 @@
+use yew::prelude::*;
+
+use enigma::table::LateralRow;
+use enigma::units::{Quantity, UnitSystem};
+
+#[derive(Properties, PartialEq)]
+pub struct LateralTableProps {
+    pub rows: Vec<LateralRow>,
+    pub units: UnitSystem,
+}
+
+#[function_component]
+pub fn LateralTable(props: &LateralTableProps) -> Html {
+    let units = props.units;
+    let header = |name: &str, quantity| format!("{} ({})", name, units.unit(quantity));
+    let length = |value| format!("{:.1}", units.from_si(Quantity::Length, value));
+
+    html! {
+        <table>
+            <thead>
+                <tr>
+                    <th>{header("Range", Quantity::Distance)}</th>
+                    <th>{header("Spin Drift", Quantity::Length)}</th>
+                    <th>{header("Coriolis", Quantity::Length)}</th>
+                    <th>{header("Wind Drift", Quantity::Length)}</th>
+                    <th>{header("Other", Quantity::Length)}</th>
+                    <th>{header("Total", Quantity::Length)}</th>
+                </tr>
+            </thead>
+            <tbody>
+                { for props.rows.iter().map(|row| html! {
+                    <tr>
+                        <td>{format!("{:.0}", units.from_si(Quantity::Distance, row.range))}</td>
+                        <td>{length(row.spin_drift)}</td>
+                        <td>{length(row.coriolis)}</td>
+                        <td>{length(row.crosswind)}</td>
+                        <td>{length(row.other)}</td>
+                        <td>{length(row.total)}</td>
+                    </tr>
+                }) }
+            </tbody>
+        </table>
+    }
+}
@@ -0,0 +1,7 @@
+pub mod chart;
+pub mod comparison_table;
+pub mod flight_canvas;
+pub mod help_tip;
+pub mod lateral_table;
+pub mod range_card;
+pub mod range_table;
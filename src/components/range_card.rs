@@ -0,0 +1,68 @@
+use yew::prelude::*;
+
+use enigma::scope::Clicks;
+use enigma::table::CardRow;
+use enigma::units::{AngleUnit, Quantity, UnitSystem};
+
+#[derive(Properties, PartialEq)]
+pub struct RangeCardProps {
+    pub rows: Vec<CardRow>,
+    pub units: UnitSystem,
+    pub click_value: f64,
+    pub click_unit: AngleUnit,
+}
+
+/// The drop chart as a card to print and tape to the rifle: just the dial
+/// and hold at each range.
+#[function_component]
+pub fn RangeCard(props: &RangeCardProps) -> Html {
+    let units = props.units;
+    // Which way to turn the turret, so the card reads without a sign
+    // convention to remember.
+    let dial = |clicks: Option<Clicks>, positive: &str, negative: &str| match clicks {
+        Some(clicks) if clicks.count == 0 => "0".to_string(),
+        Some(clicks) => format!(
+            "{} {}",
+            clicks.count.abs(),
+            if clicks.count > 0 { positive } else { negative },
+        ),
+        None => "–".to_string(),
+    };
+
+    html! {
+        <div class="range-card">
+            <table>
+                <caption>{format!("Clicks of {} {}", props.click_value, props.click_unit.label())}</caption>
+                <thead>
+                    <tr>
+                        <th rowspan="2">{format!("Range ({})", units.unit(Quantity::Distance))}</th>
+                        <th colspan="3">{"Elevation"}</th>
+                        <th colspan="3">{"Wind"}</th>
+                    </tr>
+                    <tr>
+                        <th>{"MIL"}</th>
+                        <th>{"MOA"}</th>
+                        <th>{"Clicks"}</th>
+                        <th>{"MIL"}</th>
+                        <th>{"MOA"}</th>
+                        <th>{"Clicks"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for props.rows.iter().map(|row| html! {
+                        <tr>
+                            <td>{format!("{:.0}", units.from_si(Quantity::Distance, row.range))}</td>
+                            <td>{format!("{:.1}", row.elevation.mil)}</td>
+                            <td>{format!("{:.1}", row.elevation.moa)}</td>
+                            <td>{dial(row.elevation_clicks, "U", "D")}</td>
+                            <td>{format!("{:.1}", row.windage.mil)}</td>
+                            <td>{format!("{:.1}", row.windage.moa)}</td>
+                            // Drift to the right is dialled left.
+                            <td>{dial(row.windage_clicks, "L", "R")}</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,78 @@
+use yew::prelude::*;
+
+use enigma::table::{LagRow, RangeRow, WindHoldRow, DROP_SIGN};
+use enigma::units::{centimeters, inches, Quantity, UnitSystem};
+
+#[derive(Properties, PartialEq)]
+pub struct RangeTableProps {
+    pub rows: Vec<RangeRow>,
+    pub units: UnitSystem,
+    /// Holds for a reference crosswind, one per row; no columns when empty.
+    #[prop_or_default]
+    pub wind_holds: Vec<WindHoldRow>,
+    /// The reference crosswind's speed in m/s.
+    #[prop_or_default]
+    pub reference_wind: f64,
+    /// Lag time, one per row; no column when empty.
+    #[prop_or_default]
+    pub lag_times: Vec<LagRow>,
+}
+
+#[function_component]
+pub fn RangeTable(props: &RangeTableProps) -> Html {
+    let units = props.units;
+    let header = |name: &str, quantity| format!("{} ({})", name, units.unit(quantity));
+    let drop_header = |unit: &str| format!("Drop ({}; {})", unit, DROP_SIGN);
+    let holds = !props.wind_holds.is_empty();
+    let lags = !props.lag_times.is_empty();
+    let per_wind = format!(
+        "per {:.1} {}",
+        units.from_si(Quantity::Velocity, props.reference_wind),
+        units.unit(Quantity::Velocity),
+    );
+
+    html! {
+        <table>
+            <thead>
+                <tr>
+                    <th>{header("Range", Quantity::Distance)}</th>
+                    <th>{drop_header(units.unit(Quantity::Length))}</th>
+                    <th>{drop_header("cm")}</th>
+                    <th>{drop_header("in")}</th>
+                    <th>{header("Drift", Quantity::Length)}</th>
+                    <th>{header("Velocity", Quantity::Velocity)}</th>
+                    <th>{"Energy (J)"}</th>
+                    <th>{"Time (s)"}</th>
+                    if lags {
+                        <th>{"Lag (s)"}</th>
+                    }
+                    if holds {
+                        <th>{format!("Wind hold {} (MIL)", per_wind)}</th>
+                        <th>{format!("Wind hold {} (MOA)", per_wind)}</th>
+                    }
+                </tr>
+            </thead>
+            <tbody>
+                { for props.rows.iter().enumerate().map(|(index, row)| html! {
+                    <tr>
+                        <td>{format!("{:.0}", units.from_si(Quantity::Distance, row.range))}</td>
+                        <td>{format!("{:.1}", units.from_si(Quantity::Length, row.height()))}</td>
+                        <td>{format!("{:.1}", centimeters(row.height()))}</td>
+                        <td>{format!("{:.2}", inches(row.height()))}</td>
+                        <td>{format!("{:.1}", units.from_si(Quantity::Length, row.drift))}</td>
+                        <td>{format!("{:.0}", units.from_si(Quantity::Velocity, row.velocity))}</td>
+                        <td>{format!("{:.0}", row.energy)}</td>
+                        <td>{format!("{:.3}", row.time)}</td>
+                        if let Some(lag) = props.lag_times.get(index).filter(|_| lags) {
+                            <td>{format!("{:.3}", lag.lag)}</td>
+                        }
+                        if let Some(hold) = props.wind_holds.get(index).filter(|_| holds) {
+                            <td>{format!("{:.2}", hold.hold.mil)}</td>
+                            <td>{format!("{:.2}", hold.hold.moa)}</td>
+                        }
+                    </tr>
+                }) }
+            </tbody>
+        </table>
+    }
+}
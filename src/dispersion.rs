@@ -0,0 +1,150 @@
+use crate::ballistics::{point_at_range, simulate_to_range, ShotParams};
+
+/// SplitMix64, small and seedable so a group can be reproduced exactly.
+#[derive(Clone, Copy, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+
+    /// Normally distributed, by the Box-Muller transform.
+    pub fn normal(&mut self, mean: f64, standard_deviation: f64) -> f64 {
+        let u = 1.0 - self.next_f64();
+        let v = self.next_f64();
+        mean + standard_deviation * (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+    }
+}
+
+/// How much each shot of a group varies from the nominal one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spread {
+    /// Standard deviation of muzzle velocity in m/s.
+    pub velocity_sd: f64,
+    /// Wind speed is drawn uniformly from this range, in m/s.
+    pub wind_min: f64,
+    pub wind_max: f64,
+}
+
+/// Where a group of shots struck a target, in meters on the target face.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Group {
+    /// `(right, up)` offsets from the bore line of every shot that arrived.
+    pub impacts: Vec<(f64, f64)>,
+    /// Mean point of impact.
+    pub mean: (f64, f64),
+    /// Largest distance between any two impacts.
+    pub extreme_spread: f64,
+}
+
+/// Fires `shots` simulated shots at a target `range` meters away, varying
+/// each by `spread`. Shots that fall short are left out.
+pub fn simulate_group(
+    params: &ShotParams,
+    range: f64,
+    spread: Spread,
+    shots: usize,
+    seed: u64,
+) -> Group {
+    let mut rng = Rng::new(seed);
+    let impacts: Vec<(f64, f64)> = (0..shots)
+        .filter_map(|_| {
+            let shot = ShotParams {
+                muzzle_velocity: rng.normal(params.muzzle_velocity, spread.velocity_sd),
+                wind: rng.uniform(spread.wind_min, spread.wind_max),
+                ..*params
+            };
+            let point = point_at_range(&simulate_to_range(&shot, range), range)?;
+            Some((point.position.z, point.position.y))
+        })
+        .collect();
+    group(impacts)
+}
+
+fn group(impacts: Vec<(f64, f64)>) -> Group {
+    if impacts.is_empty() {
+        return Group::default();
+    }
+    let count = impacts.len() as f64;
+    let mean = (
+        impacts.iter().map(|impact| impact.0).sum::<f64>() / count,
+        impacts.iter().map(|impact| impact.1).sum::<f64>() / count,
+    );
+    let extreme_spread = impacts
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| impacts[i + 1..].iter().map(move |b| (a, b)))
+        .map(|(a, b)| (a.0 - b.0).hypot(a.1 - b.1))
+        .fold(0.0, f64::max);
+    Group {
+        impacts,
+        mean,
+        extreme_spread,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> ShotParams {
+        ShotParams {
+            elevation: 0.1,
+            ..ShotParams::default()
+        }
+    }
+
+    #[test]
+    fn no_spread_means_no_group_size() {
+        let spread = Spread {
+            velocity_sd: 0.0,
+            wind_min: 2.0,
+            wind_max: 2.0,
+        };
+        let group = simulate_group(&params(), 300.0, spread, 5, 7);
+        assert_eq!(group.impacts.len(), 5);
+        assert_eq!(group.extreme_spread, 0.0);
+        assert_eq!(group.mean, group.impacts[0]);
+    }
+
+    #[test]
+    fn same_seed_same_group() {
+        let spread = Spread {
+            velocity_sd: 10.0,
+            wind_min: 0.0,
+            wind_max: 5.0,
+        };
+        let first = simulate_group(&params(), 300.0, spread, 8, 42);
+        assert_eq!(first, simulate_group(&params(), 300.0, spread, 8, 42));
+        assert!(first.extreme_spread > 0.0);
+    }
+
+    #[test]
+    fn normal_samples_have_the_requested_moments() {
+        let mut rng = Rng::new(1);
+        let samples: Vec<f64> = (0..20_000).map(|_| rng.normal(5.0, 2.0)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance =
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+        assert!((mean - 5.0).abs() < 0.05, "{mean}");
+        assert!((variance.sqrt() - 2.0).abs() < 0.05, "{variance}");
+    }
+}
@@ -0,0 +1,686 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Converts a ballistic coefficient from lb/in² to kg/m².
+pub const BC_TO_SI: f64 = 703.0696;
+
+const POUNDS_PER_KILOGRAM: f64 = 2.204_622_6;
+const METERS_PER_INCH: f64 = 0.0254;
+
+// Boxing the custom table would cost shot parameters their `Copy`, which
+// the wind profile and BC bands keep the same way; the price is the
+// `MAX_DRAG_POINTS` limit, which the UI and the parse error both state.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum DragModel {
+    /// The original `1 / (bc * caliber²)` approximation, kept for comparison.
+    Simple,
+    #[default]
+    G1,
+    /// Long boat-tail reference projectile, the usual choice for long range.
+    G7,
+    /// A user-supplied curve, e.g. from doppler radar, used in place of a
+    /// reference projectile's.
+    Custom(DragTable),
+}
+
+impl DragModel {
+    /// The built-in models. `Custom` needs a table, so isn't listed.
+    pub const ALL: [DragModel; 3] = [DragModel::Simple, DragModel::G1, DragModel::G7];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DragModel::Simple => "Simple",
+            DragModel::G1 => "G1",
+            DragModel::G7 => "G7",
+            DragModel::Custom(_) => "Custom",
+        }
+    }
+}
+
+impl FromStr for DragModel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DragModel::ALL
+            .into_iter()
+            .find(|model| model.label() == s)
+            .ok_or(())
+    }
+}
+
+/// Enough for the standard tables and a finely sampled doppler curve.
+pub const MAX_DRAG_POINTS: usize = 128;
+
+/// A `(mach, cd)` curve in ascending Mach, kept fixed-size so shot parameters
+/// stay `Copy`. Serialized as a plain list of pairs.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(into = "Vec<(f64, f64)>", try_from = "Vec<(f64, f64)>")]
+pub struct DragTable {
+    points: [(f64, f64); MAX_DRAG_POINTS],
+    len: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DragTableError {
+    Empty,
+    TooLong(usize),
+    /// The 1-based line that isn't a pair of numbers.
+    Malformed(usize),
+    /// The 1-based point whose Mach doesn't increase on the one before.
+    NotMonotonic(usize),
+}
+
+impl fmt::Display for DragTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DragTableError::Empty => write!(f, "the drag table has no points"),
+            DragTableError::TooLong(len) => write!(
+                f,
+                "the drag table has {} points, but custom tables are limited to {}; \
+                 thin it out and try again",
+                len, MAX_DRAG_POINTS
+            ),
+            DragTableError::Malformed(line) => {
+                write!(f, "line {} is not a mach,cd pair", line)
+            }
+            DragTableError::NotMonotonic(point) => write!(
+                f,
+                "point {} does not have a higher Mach than the one before",
+                point
+            ),
+        }
+    }
+}
+
+impl DragTable {
+    /// Checks that `points` is non-empty, finite and strictly ascending in
+    /// Mach.
+    pub fn new(points: &[(f64, f64)]) -> Result<Self, DragTableError> {
+        if points.is_empty() {
+            return Err(DragTableError::Empty);
+        }
+        if points.len() > MAX_DRAG_POINTS {
+            return Err(DragTableError::TooLong(points.len()));
+        }
+        for (index, pair) in points.windows(2).enumerate() {
+            if pair[1].0 <= pair[0].0 {
+                return Err(DragTableError::NotMonotonic(index + 2));
+            }
+        }
+        if let Some(index) = points
+            .iter()
+            .position(|(mach, cd)| !mach.is_finite() || !cd.is_finite())
+        {
+            return Err(DragTableError::Malformed(index + 1));
+        }
+
+        let mut table = DragTable {
+            points: [(0.0, 0.0); MAX_DRAG_POINTS],
+            len: points.len(),
+        };
+        table.points[..points.len()].copy_from_slice(points);
+        Ok(table)
+    }
+
+    /// Reads one `mach,cd` pair per line. Blank lines are skipped, and so is
+    /// the first line if it is a header rather than numbers.
+    pub fn from_csv(csv: &str) -> Result<Self, DragTableError> {
+        let mut points = Vec::new();
+        for (index, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let pair = line
+                .split_once(',')
+                .and_then(|(mach, cd)| Some((mach.trim().parse().ok()?, cd.trim().parse().ok()?)));
+            match pair {
+                Some(pair) => points.push(pair),
+                None if index == 0 => continue,
+                None => return Err(DragTableError::Malformed(index + 1)),
+            }
+        }
+        DragTable::new(&points)
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points[..self.len]
+    }
+}
+
+impl From<DragTable> for Vec<(f64, f64)> {
+    fn from(table: DragTable) -> Self {
+        table.points().to_vec()
+    }
+}
+
+impl TryFrom<Vec<(f64, f64)>> for DragTable {
+    type Error = DragTableError;
+
+    fn try_from(points: Vec<(f64, f64)>) -> Result<Self, Self::Error> {
+        DragTable::new(&points)
+    }
+}
+
+/// Most manufacturers publish at most four velocity steps.
+pub const MAX_BC_BANDS: usize = 4;
+
+/// A ballistic coefficient that applies from `velocity` m/s upward.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BcBand {
+    pub velocity: f64,
+    pub ballistic_coefficient: f64,
+}
+
+/// Stepped ballistic coefficients, kept fixed-size so shot parameters stay
+/// `Copy`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BcBands([Option<BcBand>; MAX_BC_BANDS]);
+
+impl BcBands {
+    /// Keeps the first `MAX_BC_BANDS` bands.
+    pub fn from_slice(bands: &[BcBand]) -> Self {
+        let mut slots = [None; MAX_BC_BANDS];
+        for (slot, band) in slots.iter_mut().zip(bands) {
+            *slot = Some(*band);
+        }
+        BcBands(slots)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = BcBand> + '_ {
+        self.0.iter().flatten().copied()
+    }
+
+    /// The coefficient of the fastest band at or below `v`, or of the slowest
+    /// band when `v` is below all of them. `None` when there are no bands.
+    pub fn at(&self, v: f64) -> Option<f64> {
+        let active = self
+            .iter()
+            .filter(|band| band.velocity <= v)
+            .max_by(|a, b| a.velocity.total_cmp(&b.velocity));
+        active
+            .or_else(|| self.iter().min_by(|a, b| a.velocity.total_cmp(&b.velocity)))
+            .map(|band| band.ballistic_coefficient)
+    }
+}
+
+/// Standard G1 drag coefficient as a function of Mach number.
+const G1_TABLE: &[(f64, f64)] = &[
+    (0.00, 0.2629),
+    (0.05, 0.2558),
+    (0.10, 0.2487),
+    (0.15, 0.2413),
+    (0.20, 0.2344),
+    (0.25, 0.2278),
+    (0.30, 0.2214),
+    (0.35, 0.2155),
+    (0.40, 0.2104),
+    (0.45, 0.2061),
+    (0.50, 0.2032),
+    (0.55, 0.2020),
+    (0.60, 0.2034),
+    (0.70, 0.2165),
+    (0.725, 0.2230),
+    (0.75, 0.2313),
+    (0.775, 0.2417),
+    (0.80, 0.2546),
+    (0.825, 0.2706),
+    (0.85, 0.2901),
+    (0.875, 0.3136),
+    (0.90, 0.3415),
+    (0.925, 0.3734),
+    (0.95, 0.4084),
+    (0.975, 0.4448),
+    (1.00, 0.4805),
+    (1.025, 0.5136),
+    (1.05, 0.5427),
+    (1.075, 0.5677),
+    (1.10, 0.5883),
+    (1.125, 0.6053),
+    (1.15, 0.6191),
+    (1.20, 0.6393),
+    (1.25, 0.6518),
+    (1.30, 0.6589),
+    (1.35, 0.6621),
+    (1.40, 0.6625),
+    (1.45, 0.6607),
+    (1.50, 0.6573),
+    (1.55, 0.6528),
+    (1.60, 0.6474),
+    (1.65, 0.6413),
+    (1.70, 0.6347),
+    (1.75, 0.6280),
+    (1.80, 0.6210),
+    (1.85, 0.6141),
+    (1.90, 0.6072),
+    (1.95, 0.6003),
+    (2.00, 0.5934),
+    (2.05, 0.5867),
+    (2.10, 0.5804),
+    (2.15, 0.5743),
+    (2.20, 0.5685),
+    (2.25, 0.5630),
+    (2.30, 0.5577),
+    (2.35, 0.5527),
+    (2.40, 0.5481),
+    (2.45, 0.5438),
+    (2.50, 0.5397),
+    (2.60, 0.5325),
+    (2.70, 0.5264),
+    (2.80, 0.5211),
+    (2.90, 0.5168),
+    (3.00, 0.5133),
+    (3.10, 0.5105),
+    (3.20, 0.5084),
+    (3.30, 0.5067),
+    (3.40, 0.5054),
+    (3.50, 0.5040),
+    (3.60, 0.5030),
+    (3.70, 0.5022),
+    (3.80, 0.5016),
+    (3.90, 0.5010),
+    (4.00, 0.5006),
+    (4.20, 0.4998),
+    (4.40, 0.4995),
+    (4.60, 0.4992),
+    (4.80, 0.4990),
+    (5.00, 0.4988),
+];
+
+/// Standard G7 drag coefficient as a function of Mach number.
+#[allow(clippy::approx_constant)]
+const G7_TABLE: &[(f64, f64)] = &[
+    (0.00, 0.1198),
+    (0.05, 0.1197),
+    (0.10, 0.1196),
+    (0.15, 0.1194),
+    (0.20, 0.1193),
+    (0.25, 0.1194),
+    (0.30, 0.1194),
+    (0.35, 0.1194),
+    (0.40, 0.1193),
+    (0.45, 0.1193),
+    (0.50, 0.1194),
+    (0.55, 0.1193),
+    (0.60, 0.1194),
+    (0.65, 0.1197),
+    (0.70, 0.1202),
+    (0.725, 0.1207),
+    (0.75, 0.1215),
+    (0.775, 0.1226),
+    (0.80, 0.1242),
+    (0.825, 0.1266),
+    (0.85, 0.1306),
+    (0.875, 0.1368),
+    (0.90, 0.1464),
+    (0.925, 0.1660),
+    (0.95, 0.2054),
+    (0.975, 0.2993),
+    (1.00, 0.3803),
+    (1.025, 0.4015),
+    (1.05, 0.4043),
+    (1.075, 0.4034),
+    (1.10, 0.4014),
+    (1.125, 0.3987),
+    (1.15, 0.3955),
+    (1.20, 0.3884),
+    (1.25, 0.3810),
+    (1.30, 0.3732),
+    (1.35, 0.3657),
+    (1.40, 0.3580),
+    (1.50, 0.3440),
+    (1.55, 0.3376),
+    (1.60, 0.3315),
+    (1.65, 0.3260),
+    (1.70, 0.3209),
+    (1.75, 0.3160),
+    (1.80, 0.3117),
+    (1.85, 0.3078),
+    (1.90, 0.3042),
+    (1.95, 0.3010),
+    (2.00, 0.2980),
+    (2.05, 0.2951),
+    (2.10, 0.2922),
+    (2.15, 0.2892),
+    (2.20, 0.2864),
+    (2.25, 0.2835),
+    (2.30, 0.2807),
+    (2.35, 0.2779),
+    (2.40, 0.2752),
+    (2.45, 0.2725),
+    (2.50, 0.2697),
+    (2.55, 0.2670),
+    (2.60, 0.2643),
+    (2.65, 0.2615),
+    (2.70, 0.2588),
+    (2.75, 0.2561),
+    (2.80, 0.2533),
+    (2.85, 0.2506),
+    (2.90, 0.2479),
+    (2.95, 0.2451),
+    (3.00, 0.2424),
+    (3.10, 0.2368),
+    (3.20, 0.2313),
+    (3.30, 0.2258),
+    (3.40, 0.2205),
+    (3.50, 0.2154),
+    (3.60, 0.2106),
+    (3.70, 0.2060),
+    (3.80, 0.2017),
+    (3.90, 0.1975),
+    (4.00, 0.1935),
+    (4.20, 0.1861),
+    (4.40, 0.1793),
+    (4.60, 0.1730),
+    (4.80, 0.1672),
+    (5.00, 0.1618),
+];
+
+/// Linearly interpolates a `(mach, cd)` table, clamping outside its range.
+fn interpolate(table: &[(f64, f64)], mach: f64) -> f64 {
+    let (first_mach, first_cd) = table[0];
+    if mach <= first_mach {
+        return first_cd;
+    }
+
+    for pair in table.windows(2) {
+        let (m0, cd0) = pair[0];
+        let (m1, cd1) = pair[1];
+        if mach <= m1 {
+            return cd0 + (cd1 - cd0) * (mach - m0) / (m1 - m0);
+        }
+    }
+
+    table[table.len() - 1].1
+}
+
+pub fn g1_drag_coefficient(mach: f64) -> f64 {
+    interpolate(G1_TABLE, mach)
+}
+
+pub fn g7_drag_coefficient(mach: f64) -> f64 {
+    interpolate(G7_TABLE, mach)
+}
+
+impl DragModel {
+    /// The reference projectile's drag coefficient at `mach`, or `None` for
+    /// `Simple`, which has no Mach dependence.
+    pub fn drag_coefficient(self, mach: f64) -> Option<f64> {
+        match self {
+            DragModel::Simple => None,
+            DragModel::G1 => Some(g1_drag_coefficient(mach)),
+            DragModel::G7 => Some(g7_drag_coefficient(mach)),
+            DragModel::Custom(table) => Some(interpolate(table.points(), mach)),
+        }
+    }
+}
+
+/// `(mach, cd)` for `model` at `samples` even steps from Mach 0 to
+/// `max_mach`, both ends included, for plotting. Empty for `Simple`.
+pub fn drag_curve(model: DragModel, max_mach: f64, samples: usize) -> Vec<(f64, f64)> {
+    (0..=samples)
+        .map_while(|i| {
+            let mach = max_mach * i as f64 / samples.max(1) as f64;
+            model.drag_coefficient(mach).map(|cd| (mach, cd))
+        })
+        .collect()
+}
+
+/// Drag deceleration magnitude in m/s² for a projectile moving at `v` m/s.
+///
+/// For the standard models the ballistic coefficient (lb/in²) scales the
+/// reference projectile's drag, so the caliber only matters for `Simple`.
+///
+/// A zero or negative coefficient or caliber would divide by zero, so those
+/// yield no drag at all rather than an infinity that turns the trajectory
+/// into NaN. Inputs are validated before they get here.
+pub fn deceleration(
+    model: DragModel,
+    v: f64,
+    caliber: f64,
+    ballistic_coefficient: f64,
+    air_density: f64,
+    speed_of_sound: f64,
+) -> f64 {
+    let degenerate = ballistic_coefficient < f64::EPSILON
+        || (model == DragModel::Simple && caliber < f64::EPSILON);
+    if degenerate || ballistic_coefficient.is_nan() {
+        return 0.0;
+    }
+
+    match model {
+        DragModel::Simple => {
+            let drag_coefficient = 1.0 / (ballistic_coefficient * caliber.powi(2));
+            0.5 * drag_coefficient * air_density * v.powi(2)
+        }
+        DragModel::G1 | DragModel::G7 | DragModel::Custom(_) => {
+            let cd = model
+                .drag_coefficient(v / speed_of_sound)
+                .unwrap_or_default();
+            let bc = ballistic_coefficient * BC_TO_SI;
+            air_density * v.powi(2) * cd * std::f64::consts::PI / (8.0 * bc)
+        }
+    }
+}
+
+/// Sectional density in lb/in², the same units as the ballistic coefficient.
+pub fn sectional_density(mass: f64, caliber: f64) -> f64 {
+    (mass * POUNDS_PER_KILOGRAM) / (caliber / METERS_PER_INCH).powi(2)
+}
+
+/// Form factor `i = SD / BC` against the chosen reference projectile. Values
+/// far from 1 suggest the BC doesn't fit the bullet's geometry.
+pub fn form_factor(sectional_density: f64, ballistic_coefficient: f64) -> f64 {
+    sectional_density / ballistic_coefficient
+}
+
+/// The ballistic coefficient for a given form factor, the inverse of
+/// [`form_factor`].
+pub fn ballistic_coefficient(sectional_density: f64, form_factor: f64) -> f64 {
+    sectional_density / form_factor
+}
+
+/// The coefficient against `to` that gives the same drag as
+/// `ballistic_coefficient` against `from` at `mach`. The reference shapes
+/// slow down differently, so the match only holds near that speed. `None`
+/// if either model is `Simple`.
+pub fn convert_bc(
+    ballistic_coefficient: f64,
+    from: DragModel,
+    to: DragModel,
+    mach: f64,
+) -> Option<f64> {
+    Some(ballistic_coefficient * to.drag_coefficient(mach)? / from.drag_coefficient(mach)?)
+}
+
+/// The G7 equivalent of a G1 coefficient at `mach`; see [`convert_bc`].
+pub fn g1_to_g7(bc_g1: f64, mach: f64) -> f64 {
+    bc_g1 * g7_drag_coefficient(mach) / g1_drag_coefficient(mach)
+}
+
+/// The G1 equivalent of a G7 coefficient at `mach`; see [`convert_bc`].
+pub fn g7_to_g1(bc_g7: f64, mach: f64) -> f64 {
+    bc_g7 * g1_drag_coefficient(mach) / g7_drag_coefficient(mach)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn g1_matches_table_points() {
+        assert_eq!(g1_drag_coefficient(0.0), 0.2629);
+        assert_eq!(g1_drag_coefficient(1.0), 0.4805);
+        assert_eq!(g1_drag_coefficient(2.0), 0.5934);
+    }
+
+    #[test]
+    fn g1_interpolates_between_points() {
+        let cd = g1_drag_coefficient(1.0125);
+        assert!((cd - (0.4805 + 0.5136) / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tables_clamp_outside_their_range() {
+        assert_eq!(g1_drag_coefficient(12.0), 0.4988);
+        assert_eq!(g7_drag_coefficient(12.0), 0.1618);
+        assert_eq!(g7_drag_coefficient(-1.0), 0.1198);
+    }
+
+    #[test]
+    fn drag_model_parses_its_label() {
+        for model in DragModel::ALL {
+            assert_eq!(model.label().parse(), Ok(model));
+        }
+        assert!("G8".parse::<DragModel>().is_err());
+    }
+
+    #[test]
+    fn bc_bands_pick_the_band_for_the_current_speed() {
+        let band = |velocity, ballistic_coefficient| BcBand {
+            velocity,
+            ballistic_coefficient,
+        };
+        let bands = BcBands::from_slice(&[band(800.0, 0.47), band(500.0, 0.45), band(0.0, 0.40)]);
+        assert_eq!(bands.at(850.0), Some(0.47));
+        assert_eq!(bands.at(800.0), Some(0.47));
+        assert_eq!(bands.at(600.0), Some(0.45));
+        assert_eq!(bands.at(100.0), Some(0.40));
+        assert_eq!(
+            BcBands::from_slice(&[band(500.0, 0.3)]).at(100.0),
+            Some(0.3)
+        );
+        assert_eq!(BcBands::default().at(100.0), None);
+    }
+
+    #[test]
+    fn sectional_density_of_a_168gr_308() {
+        let sd = sectional_density(168.0 * 6.479891e-5, 0.308 * METERS_PER_INCH);
+        assert!((sd - 0.253).abs() < 0.001, "{sd}");
+        assert!((form_factor(sd, 0.462) - 0.548).abs() < 0.001);
+    }
+
+    #[test]
+    fn zero_caliber_or_bc_produces_no_drag_instead_of_infinity() {
+        for model in DragModel::ALL {
+            assert_eq!(deceleration(model, 850.0, 0.0, 0.0, 1.225, 340.0), 0.0);
+            assert_eq!(deceleration(model, 850.0, 0.00762, -0.1, 1.225, 340.0), 0.0);
+        }
+        assert_eq!(
+            deceleration(DragModel::Simple, 850.0, 0.0, 0.4, 1.225, 340.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn plotted_curve_is_what_the_integrator_uses() {
+        for model in [DragModel::G1, DragModel::G7] {
+            let curve = drag_curve(model, 5.0, 100);
+            assert_eq!(curve.len(), 101);
+            assert_eq!(curve[0].0, 0.0);
+            assert_eq!(curve[100].0, 5.0);
+
+            let (air_density, speed_of_sound, bc) = (1.225, 340.0, 0.4);
+            for (mach, cd) in curve.into_iter().skip(1) {
+                let v = mach * speed_of_sound;
+                let used = deceleration(model, v, 0.00782, bc, air_density, speed_of_sound)
+                    * 8.0
+                    * bc
+                    * BC_TO_SI
+                    / (air_density * v.powi(2) * std::f64::consts::PI);
+                assert!((used - cd).abs() < 1e-12, "{model:?} at Mach {mach}");
+            }
+        }
+        // Every 0.05 step lands on a G1 table point rather than beside it.
+        let curve = drag_curve(DragModel::G1, 5.0, 100);
+        assert_eq!(curve[20], (1.0, g1_drag_coefficient(1.0)));
+        assert!(drag_curve(DragModel::Simple, 5.0, 100).is_empty());
+    }
+
+    #[test]
+    fn custom_copy_of_g1_drops_like_g1() {
+        use crate::ballistics::{simulate_to_range, ShotParams};
+
+        let custom = DragModel::Custom(DragTable::new(G1_TABLE).unwrap());
+        let drop_at_600m = |drag_model| {
+            let params = ShotParams {
+                drag_model,
+                ..ShotParams::default()
+            };
+            *simulate_to_range(&params, 600.0).last().unwrap()
+        };
+        assert_eq!(drop_at_600m(custom), drop_at_600m(DragModel::G1));
+    }
+
+    #[test]
+    fn drag_tables_read_csv_and_reject_bad_input() {
+        let table = DragTable::from_csv("mach,cd\n0.5, 0.2\n\n1.0,0.4\n").unwrap();
+        assert_eq!(table.points(), [(0.5, 0.2), (1.0, 0.4)]);
+        assert!((interpolate(table.points(), 0.75) - 0.3).abs() < 1e-12);
+
+        assert_eq!(DragTable::from_csv("mach,cd\n"), Err(DragTableError::Empty));
+        assert_eq!(
+            DragTable::from_csv("0.5,0.2\n0.6,fast\n"),
+            Err(DragTableError::Malformed(2))
+        );
+        assert_eq!(
+            DragTable::from_csv("0.5,0.2\n0.9,0.3\n0.9,0.4\n"),
+            Err(DragTableError::NotMonotonic(3))
+        );
+        let long = vec![(0.0, 0.2); MAX_DRAG_POINTS + 1];
+        assert_eq!(
+            DragTable::new(&long),
+            Err(DragTableError::TooLong(MAX_DRAG_POINTS + 1))
+        );
+        let csv: String = (0..=MAX_DRAG_POINTS)
+            .map(|i| format!("{},0.3\n", i as f64 / 100.0))
+            .collect();
+        let error = DragTable::from_csv(&csv).unwrap_err().to_string();
+        assert!(error.contains(&MAX_DRAG_POINTS.to_string()), "{error}");
+    }
+
+    #[test]
+    fn custom_models_round_trip_through_json() {
+        let model = DragModel::Custom(DragTable::new(&[(0.0, 0.3), (2.0, 0.25)]).unwrap());
+        let json = serde_json::to_string(&model).unwrap();
+        assert_eq!(json, r#"{"Custom":[[0.0,0.3],[2.0,0.25]]}"#);
+        assert_eq!(serde_json::from_str::<DragModel>(&json).unwrap(), model);
+        assert!(serde_json::from_str::<DragModel>(r#"{"Custom":[]}"#).is_err());
+        assert_eq!(serde_json::to_string(&DragModel::G7).unwrap(), r#""G7""#);
+    }
+
+    #[test]
+    fn simple_model_matches_legacy_formula() {
+        let expected = 0.5 * 1.225 * 850.0_f64.powi(2) / (0.4 * 0.00762_f64.powi(2));
+        let actual = deceleration(DragModel::Simple, 850.0, 0.00762, 0.4, 1.225, 340.0);
+        assert!((actual - expected).abs() <= expected * 1e-12);
+    }
+
+    #[test]
+    fn converts_a_published_bullet_between_g1_and_g7() {
+        // Hornady's 6.5 mm 140 gr ELD Match: G1 0.646, G7 0.326, at about
+        // 2700 fps, Mach 2.42 on a standard day.
+        let mach = 2.42;
+        let g7 = g1_to_g7(0.646, mach);
+        assert!((g7 - 0.326).abs() < 0.326 * 0.02, "{g7}");
+        assert!((g7_to_g1(g7, mach) - 0.646).abs() < 1e-12);
+        assert_eq!(
+            convert_bc(0.646, DragModel::G1, DragModel::G7, mach),
+            Some(g7)
+        );
+        assert_eq!(
+            convert_bc(0.646, DragModel::G1, DragModel::Simple, mach),
+            None
+        );
+    }
+
+    #[test]
+    fn form_factor_and_ballistic_coefficient_are_inverses() {
+        let sd = sectional_density(0.009072, 0.00671);
+        let i = form_factor(sd, 0.326);
+        assert!((ballistic_coefficient(sd, i) - 0.326).abs() < 1e-12);
+    }
+}
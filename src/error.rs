@@ -0,0 +1,33 @@
+use std::fmt;
+
+/// Why the engine couldn't produce an answer for a shot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BallisticError {
+    InvalidCaliber,
+    InvalidBc,
+    InvalidAngle,
+    InvalidVelocity,
+    InvalidTimeStep,
+    InvalidMass,
+    /// The solver ran out of iterations before settling on an answer.
+    NonConvergent,
+    /// No launch angle the solver tries reaches the target.
+    OutOfRange,
+}
+
+impl fmt::Display for BallisticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BallisticError::InvalidCaliber => "Caliber must be greater than zero",
+            BallisticError::InvalidBc => "Ballistic coefficient must be above 0 and at most 1",
+            BallisticError::InvalidAngle => "Elevation must be between 0° and 90°",
+            BallisticError::InvalidVelocity => "Muzzle velocity must be greater than zero",
+            BallisticError::InvalidTimeStep => "Time step must be between 0.0005 and 0.05 s",
+            BallisticError::InvalidMass => "Bullet mass must be greater than zero",
+            BallisticError::NonConvergent => "No firing solution: the solver did not converge",
+            BallisticError::OutOfRange => "No firing solution: target is out of range",
+        })
+    }
+}
+
+impl std::error::Error for BallisticError {}
@@ -0,0 +1,229 @@
+//! What each calculator input means, shown beside it in the form. Kept
+//! together so the wording stays consistent as inputs are added.
+
+/// A short explanation of one input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Help {
+    pub label: &'static str,
+    pub body: &'static str,
+}
+
+pub const WIND: Help = Help {
+    label: "Wind",
+    body: "Wind speed at the firing point. A light breeze is 2–4 m/s (5–10 mph).",
+};
+
+pub const WIND_DIRECTION: Help = Help {
+    label: "Wind direction",
+    body: "Where the wind comes from, in degrees clockwise from the target: 0 is a headwind, \
+           90 blows from your right, 180 is a tailwind.",
+};
+
+pub const ELEVATION: Help = Help {
+    label: "Elevation",
+    body: "Angle of the bore above level. Flat-fire rifle shots need well under 1°; \
+           use Zero or Solve to find it.",
+};
+
+pub const CALIBER: Help = Help {
+    label: "Caliber",
+    body: "Bullet diameter, e.g. 7.82 mm (0.308 in) for a .308.",
+};
+
+pub const BALLISTIC_COEFFICIENT: Help = Help {
+    label: "Ballistic coefficient",
+    body: "How well the bullet keeps its speed compared with the drag model's reference \
+           projectile. Higher is slicker; rifle bullets run about 0.2–0.7 G1 or 0.1–0.35 G7.",
+};
+
+pub const MUZZLE_VELOCITY: Help = Help {
+    label: "Muzzle velocity",
+    body: "Speed leaving the barrel, ideally from a chronograph. Rifles run about \
+           750–1000 m/s (2500–3300 fps).",
+};
+
+pub const DRAG_MODEL: Help = Help {
+    label: "Drag model",
+    body: "The reference projectile the BC is quoted against. G1 suits flat-based bullets, \
+           G7 long boat-tails; use whichever the maker publishes the BC for.",
+};
+
+pub const BC_CONVERTER: Help = Help {
+    label: "BC converter",
+    body: "Turns a G1 coefficient into the G7 one that slows the bullet the same at the \
+           muzzle, or back. Makers often publish only one; the match drifts at lower speeds.",
+};
+
+pub const DRAG_TABLE: Help = Help {
+    label: "Custom drag table",
+    body: "A measured drag curve, e.g. from doppler radar, to use instead of a reference \
+           projectile: one mach,cd pair per line in rising Mach, up to 128 points.",
+};
+
+pub const LATITUDE: Help = Help {
+    label: "Latitude",
+    body: "Where you're shooting from, positive north. Only used for Coriolis, which matters \
+           past about 800 m.",
+};
+
+pub const AZIMUTH: Help = Help {
+    label: "Azimuth",
+    body: "Compass direction of the shot, in degrees clockwise from true north.",
+};
+
+pub const TWIST_RATE: Help = Help {
+    label: "Twist rate",
+    body: "Barrel length per full turn of the rifling, e.g. 10 for a 1:10\" barrel. \
+           Faster twists (smaller numbers) stabilise longer bullets.",
+};
+
+pub const BULLET_LENGTH: Help = Help {
+    label: "Bullet length",
+    body: "Overall length of the bullet alone, used for stability. About 31 mm (1.2 in) for \
+           a 168 gr .308.",
+};
+
+pub const MASS: Help = Help {
+    label: "Mass",
+    body: "Bullet weight, e.g. 168 gr (10.9 g) for a .308 match bullet.",
+};
+
+pub const ALTITUDE: Help = Help {
+    label: "Altitude",
+    body: "Height of the firing point above sea level. Thinner air higher up means less drag.",
+};
+
+pub const TEMPERATURE: Help = Help {
+    label: "Temperature",
+    body: "Air temperature corrected to sea level; 15 °C is a standard day.",
+};
+
+pub const PRESSURE: Help = Help {
+    label: "Pressure",
+    body: "Barometric pressure corrected to sea level, as weather reports give it; \
+           1013.25 hPa is a standard day.",
+};
+
+pub const HUMIDITY: Help = Help {
+    label: "Humidity",
+    body: "Relative humidity, 0–100%. Damp air is slightly thinner, but the effect is small.",
+};
+
+pub const DENSITY_ALTITUDE: Help = Help {
+    label: "Density altitude",
+    body: "Temperature, pressure and humidity rolled into one number: the height at which a \
+           standard day has the same air. Filled in from the fields above, or type one in to \
+           set the pressure to match.",
+};
+
+pub const VELOCITY_TEMP_SENSITIVITY: Help = Help {
+    label: "Velocity temperature sensitivity",
+    body: "How much the muzzle velocity changes per degree of powder temperature; \
+           typically 0.3–1.5 m/s per °C. Leave at 0 to ignore.",
+};
+
+pub const REFERENCE_TEMPERATURE: Help = Help {
+    label: "Reference temperature",
+    body: "Powder temperature the muzzle velocity was measured at.",
+};
+
+pub const INTEGRATOR: Help = Help {
+    label: "Integrator",
+    body: "How the flight is stepped through time. RK4 is accurate; Euler is kept to compare.",
+};
+
+pub const TIME_STEP: Help = Help {
+    label: "Time step",
+    body: "Seconds between integration steps, 0.0005–0.05. Smaller is more accurate and slower; \
+           0.01 is plenty with RK4.",
+};
+
+pub const MAX_FLIGHT_TIME: Help = Help {
+    label: "Max flight time",
+    body: "Seconds after which the simulation stops even if the shot hasn't come down; \
+           120 s unless set.",
+};
+
+pub const MAX_RANGE: Help = Help {
+    label: "Max range",
+    body: "Distance after which the simulation stops even if the shot hasn't come down; \
+           10 km unless set.",
+};
+
+pub const ZERO_DISTANCE: Help = Help {
+    label: "Zero distance",
+    body: "Range at which the sights are set to hit point of aim, commonly 100 m or 100 yd.",
+};
+
+pub const SIGHT_HEIGHT: Help = Help {
+    label: "Sight height",
+    body: "Distance from the centre of the bore to the centre of the scope, \
+           usually 38–65 mm (1.5–2.5 in).",
+};
+
+pub const LOOK_ANGLE: Help = Help {
+    label: "Look angle",
+    body: "Slope to the target, positive uphill. Up or down, a slope shortens the drop.",
+};
+
+pub const CLICK_VALUE: Help = Help {
+    label: "Click value",
+    body: "How far one turret click moves the point of impact: often 0.25 MOA or 0.1 MIL.",
+};
+
+/// Every entry, for checking them all at once.
+pub const ALL: [Help; 29] = [
+    WIND,
+    WIND_DIRECTION,
+    ELEVATION,
+    CALIBER,
+    BALLISTIC_COEFFICIENT,
+    MUZZLE_VELOCITY,
+    DRAG_MODEL,
+    BC_CONVERTER,
+    DRAG_TABLE,
+    LATITUDE,
+    AZIMUTH,
+    TWIST_RATE,
+    BULLET_LENGTH,
+    MASS,
+    ALTITUDE,
+    TEMPERATURE,
+    PRESSURE,
+    HUMIDITY,
+    DENSITY_ALTITUDE,
+    VELOCITY_TEMP_SENSITIVITY,
+    REFERENCE_TEMPERATURE,
+    INTEGRATOR,
+    TIME_STEP,
+    MAX_FLIGHT_TIME,
+    MAX_RANGE,
+    ZERO_DISTANCE,
+    SIGHT_HEIGHT,
+    LOOK_ANGLE,
+    CLICK_VALUE,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drag_table_help_gives_the_real_limit() {
+        let limit = crate::drag::MAX_DRAG_POINTS.to_string();
+        assert!(DRAG_TABLE.body.contains(&limit), "{DRAG_TABLE:?}");
+    }
+
+    #[test]
+    fn every_entry_is_filled_in_and_distinct() {
+        for (index, help) in ALL.iter().enumerate() {
+            assert!(!help.label.is_empty() && !help.body.is_empty(), "{help:?}");
+            assert!(
+                ALL[index + 1..]
+                    .iter()
+                    .all(|other| other.label != help.label),
+                "{help:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+
+/// How many snapshots are kept before the oldest are forgotten.
+pub const MAX_HISTORY: usize = 50;
+
+/// Snapshots to step back and forth through, like an editor's undo stack.
+///
+/// Recording after an undo throws away everything that had been undone, and
+/// only the last [`MAX_HISTORY`] snapshots are kept.
+#[derive(Clone, Debug, PartialEq)]
+pub struct History<T> {
+    entries: VecDeque<T>,
+    /// Index of the snapshot being shown, when there is one.
+    cursor: usize,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        History {
+            entries: VecDeque::new(),
+            cursor: 0,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> History<T> {
+    /// Makes `snapshot` the current state. Recording the current state again
+    /// changes nothing.
+    pub fn record(&mut self, snapshot: T) {
+        if self.current() == Some(&snapshot) {
+            return;
+        }
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.cursor + 1);
+        }
+        self.entries.push_back(snapshot);
+        if self.entries.len() > MAX_HISTORY {
+            self.entries.pop_front();
+        }
+        self.cursor = self.entries.len() - 1;
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.entries.get(self.cursor)
+    }
+
+    /// Steps back, returning the snapshot to restore.
+    pub fn undo(&mut self) -> Option<T> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.cursor -= 1;
+        self.current().cloned()
+    }
+
+    /// Steps forward again after an undo.
+    pub fn redo(&mut self) -> Option<T> {
+        if !self.can_redo() {
+            return None;
+        }
+        self.cursor += 1;
+        self.current().cloned()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.cursor + 1 < self.entries.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_walk_the_stack() {
+        let mut history = History::default();
+        assert_eq!(history.undo(), None);
+        history.record(1);
+        history.record(2);
+        history.record(3);
+
+        assert_eq!(history.undo(), Some(2));
+        assert_eq!(history.undo(), Some(1));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(2));
+        assert_eq!(history.redo(), Some(3));
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.current(), Some(&3));
+    }
+
+    #[test]
+    fn recording_after_undo_drops_the_redo_branch() {
+        let mut history = History::default();
+        for snapshot in [1, 2, 3] {
+            history.record(snapshot);
+        }
+        history.undo();
+        history.undo();
+        history.record(4);
+
+        assert!(!history.can_redo());
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.undo(), Some(1));
+        assert_eq!(history.redo(), Some(4));
+    }
+
+    #[test]
+    fn repeats_are_not_recorded() {
+        let mut history = History::default();
+        history.record(1);
+        history.record(1);
+        assert_eq!(history.len(), 1);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn oldest_snapshots_fall_off_the_end() {
+        let mut history = History::default();
+        for snapshot in 0..MAX_HISTORY + 10 {
+            history.record(snapshot);
+        }
+        assert_eq!(history.len(), MAX_HISTORY);
+        while history.can_undo() {
+            history.undo();
+        }
+        assert_eq!(history.current(), Some(&10));
+    }
+}
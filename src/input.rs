@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsCast;
+#[cfg(target_arch = "wasm32")]
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+
+/// The `<input>` that fired `e`, if any.
+#[cfg(target_arch = "wasm32")]
+pub fn input_target(e: &Event) -> Option<HtmlInputElement> {
+    e.target()?.dyn_into().ok()
+}
+
+/// The text in the `<textarea>` that fired `e`.
+#[cfg(target_arch = "wasm32")]
+pub fn textarea_value(e: &Event) -> Option<String> {
+    let area: HtmlTextAreaElement = e.target()?.dyn_into().ok()?;
+    Some(area.value())
+}
+
+/// Parses the value of the `<input>` that fired `e`. Events without an input
+/// target and values that don't parse both come back as `None`.
+#[cfg(target_arch = "wasm32")]
+pub fn parse_input<T: FromStr>(e: &Event) -> Option<T> {
+    parse_value(&input_target(e)?.value())
+}
+
+/// Parses the selected value of the `<select>` that fired `e`.
+#[cfg(target_arch = "wasm32")]
+pub fn parse_select<T: FromStr>(e: &Event) -> Option<T> {
+    let select: HtmlSelectElement = e.target()?.dyn_into().ok()?;
+    parse_value(&select.value())
+}
+
+pub fn parse_value<T: FromStr>(text: &str) -> Option<T> {
+    text.trim().parse().ok()
+}
+
+/// Whether the `<input>` that fired `e` holds text the browser couldn't read
+/// as a number. Number inputs report such text as an empty value, so this is
+/// the only way to tell it apart from a cleared field.
+#[cfg(target_arch = "wasm32")]
+pub fn has_bad_input(e: &Event) -> bool {
+    input_target(e).is_some_and(|input| input.validity().bad_input())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_and_rejects_junk() {
+        assert_eq!(parse_value::<f64>("850"), Some(850.0));
+        assert_eq!(parse_value::<f64>(" -1.5 "), Some(-1.5));
+        assert_eq!(parse_value::<f64>(""), None);
+        assert_eq!(parse_value::<f64>("12abc"), None);
+        assert_eq!(parse_value::<u32>("-3"), None);
+    }
+}
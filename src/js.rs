@@ -0,0 +1,81 @@
+//! Thin `wasm-bindgen` adapters so pages can call the engine without the UI.
+//!
+//! Shot parameters are plain objects with the same snake_case fields as a
+//! saved profile's `shot`; any that are left out take their defaults.
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::ballistics::{self, ShotParams};
+use crate::error::BallisticError;
+use crate::{solver, table, validation};
+
+fn params(value: JsValue) -> Result<ShotParams, JsError> {
+    serde_wasm_bindgen::from_value(value).map_err(|err| JsError::new(&err.to_string()))
+}
+
+fn to_js(value: &impl serde::Serialize) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// `simulate(params) -> TrajectoryPoint[]`
+///
+/// Each point is `{ time, position: { x, y, z }, velocity: { x, y, z },
+/// coriolis_drift, wind_drift }` in seconds, meters and m/s, ending where the shot comes
+/// back to muzzle height. Throws if the parameters can't be simulated.
+#[wasm_bindgen]
+pub fn simulate(params: JsValue) -> Result<JsValue, JsError> {
+    to_js(&ballistics::simulate(&self::params(params)?)?)
+}
+
+#[derive(Deserialize)]
+struct RangeTableRequest {
+    #[serde(default)]
+    params: ShotParams,
+    step: f64,
+    max: f64,
+    #[serde(default)]
+    sight_height: f64,
+}
+
+/// `rangeTable({ params, step, max, sight_height }) -> RangeRow[]`
+///
+/// Rows are `{ range, drop, drift, velocity, energy, time }` every `step`
+/// meters out to `max`, all in SI units, with `drop` positive below the line
+/// of sight as in [`RangeRow`](crate::table::RangeRow). Throws if the parameters can't be
+/// simulated or `step` and `max` aren't both positive.
+#[wasm_bindgen(js_name = rangeTable)]
+pub fn range_table(request: JsValue) -> Result<JsValue, JsError> {
+    let request: RangeTableRequest =
+        serde_wasm_bindgen::from_value(request).map_err(|err| JsError::new(&err.to_string()))?;
+    validation::check(&request.params)?;
+    if !(request.step > 0.0 && request.max > 0.0) {
+        return Err(JsError::new("step and max must both be positive"));
+    }
+    let points = ballistics::simulate_to_range(&request.params, request.max);
+    to_js(&table::range_table(
+        &points,
+        request.step,
+        request.max,
+        request.params.mass,
+        request.sight_height,
+    ))
+}
+
+/// `solveElevation(params, distance, height) -> number | undefined`
+///
+/// Launch elevation in degrees that hits a point `distance` meters downrange
+/// and `height` meters above the muzzle, or `undefined` if it's out of reach.
+/// Throws if the parameters can't be simulated.
+#[wasm_bindgen(js_name = solveElevation)]
+pub fn solve_elevation(
+    params: JsValue,
+    distance: f64,
+    height: f64,
+) -> Result<Option<f64>, JsError> {
+    match solver::solve_elevation(&self::params(params)?, distance, height) {
+        Ok(elevation) => Ok(Some(elevation)),
+        Err(BallisticError::OutOfRange | BallisticError::NonConvergent) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,24 @@
+pub mod atmosphere;
+pub mod ballistics;
+pub mod cartridge;
+pub mod dispersion;
+pub mod drag;
+pub mod error;
+pub mod help;
+pub mod history;
+#[cfg(target_arch = "wasm32")]
+pub mod js;
+pub mod plot;
+pub mod profile;
+pub mod scope;
+pub mod shortcuts;
+pub mod solver;
+pub mod spin;
+pub mod state;
+pub mod table;
+pub mod timestep;
+pub mod units;
+pub mod validation;
+pub mod wind;
+#[cfg(target_arch = "wasm32")]
+pub mod worker;
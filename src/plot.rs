@@ -0,0 +1,138 @@
+/// Data-space extents of a chart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min_x: f64,
+    pub max_x: f64,
+    pub min_y: f64,
+    pub max_y: f64,
+}
+
+impl Bounds {
+    /// Smallest bounds containing every point, widened so neither axis is
+    /// zero-sized. `None` for an empty series.
+    pub fn of(points: &[(f64, f64)]) -> Option<Bounds> {
+        let (&(x, y), rest) = points.split_first()?;
+        let mut bounds = Bounds {
+            min_x: x,
+            max_x: x,
+            min_y: y,
+            max_y: y,
+        };
+        for &(x, y) in rest {
+            bounds.min_x = bounds.min_x.min(x);
+            bounds.max_x = bounds.max_x.max(x);
+            bounds.min_y = bounds.min_y.min(y);
+            bounds.max_y = bounds.max_y.max(y);
+        }
+        if bounds.max_x == bounds.min_x {
+            bounds.max_x += 1.0;
+        }
+        if bounds.max_y == bounds.min_y {
+            bounds.max_y += 1.0;
+        }
+        Some(bounds)
+    }
+}
+
+/// Pixel area a chart is drawn into, with a margin reserved for labels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub width: f64,
+    pub height: f64,
+    pub margin: f64,
+}
+
+impl Viewport {
+    /// Maps a data point to SVG coordinates, flipping y so up is up.
+    pub fn to_screen(&self, bounds: &Bounds, (x, y): (f64, f64)) -> (f64, f64) {
+        let plot_width = self.width - 2.0 * self.margin;
+        let plot_height = self.height - 2.0 * self.margin;
+        (
+            self.margin + (x - bounds.min_x) / (bounds.max_x - bounds.min_x) * plot_width,
+            self.height
+                - self.margin
+                - (y - bounds.min_y) / (bounds.max_y - bounds.min_y) * plot_height,
+        )
+    }
+}
+
+/// Screen positions of the first `count` points of `path` followed by `head`.
+pub fn trace(
+    viewport: &Viewport,
+    bounds: &Bounds,
+    path: &[(f64, f64)],
+    count: usize,
+    head: (f64, f64),
+) -> Vec<(f64, f64)> {
+    path.iter()
+        .take(count)
+        .copied()
+        .chain([head])
+        .map(|point| viewport.to_screen(bounds, point))
+        .collect()
+}
+
+/// Evenly spaced "nice" tick values (1, 2 or 5 times a power of ten) covering
+/// `min..=max` with roughly `count` ticks.
+pub fn ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    if max <= min || !max.is_finite() || !min.is_finite() || count == 0 {
+        return vec![min];
+    }
+    let raw_step = (max - min) / count as f64;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(10.0 * magnitude);
+
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_use_round_steps() {
+        assert_eq!(
+            ticks(0.0, 1000.0, 5),
+            vec![0.0, 200.0, 400.0, 600.0, 800.0, 1000.0]
+        );
+        assert_eq!(ticks(-0.3, 0.3, 3), vec![-0.2, 0.0, 0.2]);
+    }
+
+    #[test]
+    fn screen_mapping_flips_y() {
+        let viewport = Viewport {
+            width: 100.0,
+            height: 50.0,
+            margin: 10.0,
+        };
+        let bounds = Bounds::of(&[(0.0, 0.0), (10.0, 5.0)]).unwrap();
+        assert_eq!(viewport.to_screen(&bounds, (0.0, 0.0)), (10.0, 40.0));
+        assert_eq!(viewport.to_screen(&bounds, (10.0, 5.0)), (90.0, 10.0));
+    }
+
+    #[test]
+    fn trace_stops_at_the_head() {
+        let viewport = Viewport {
+            width: 100.0,
+            height: 50.0,
+            margin: 10.0,
+        };
+        let path = [(0.0, 0.0), (5.0, 5.0), (10.0, 0.0)];
+        let bounds = Bounds::of(&path).unwrap();
+        let trace = trace(&viewport, &bounds, &path, 2, (7.5, 2.5));
+        assert_eq!(trace, vec![(10.0, 40.0), (50.0, 10.0), (70.0, 25.0)]);
+    }
+
+    #[test]
+    fn degenerate_bounds_are_widened() {
+        let bounds = Bounds::of(&[(1.0, 2.0)]).unwrap();
+        assert!(bounds.max_x > bounds.min_x && bounds.max_y > bounds.min_y);
+        assert!(Bounds::of(&[]).is_none());
+    }
+}
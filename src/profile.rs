@@ -0,0 +1,510 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ballistics::ShotParams;
+use crate::drag::{BcBand, BcBands, DragModel, DragTable};
+use crate::wind::{WindProfile, WindSample};
+
+/// Bumped whenever a change would make older files load incorrectly.
+pub const PROFILE_VERSION: u32 = 1;
+
+/// Everything needed to reproduce a setup, as saved to and loaded from JSON.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShotProfile {
+    pub version: u32,
+    #[serde(default)]
+    pub shot: ShotParams,
+    /// Zero distance in meters.
+    #[serde(default = "default_zero_distance")]
+    pub zero_distance: f64,
+    /// Sight height above the bore in meters.
+    #[serde(default)]
+    pub sight_height: f64,
+}
+
+fn default_zero_distance() -> f64 {
+    100.0
+}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Malformed(serde_json::Error),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Malformed(err) => write!(f, "not a valid shot profile: {}", err),
+            ProfileError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported profile version {} (expected {})",
+                version, PROFILE_VERSION
+            ),
+        }
+    }
+}
+
+impl Default for ShotProfile {
+    fn default() -> Self {
+        ShotProfile::new(ShotParams::default(), default_zero_distance(), 0.0)
+    }
+}
+
+impl ShotProfile {
+    pub fn new(shot: ShotParams, zero_distance: f64, sight_height: f64) -> Self {
+        ShotProfile {
+            version: PROFILE_VERSION,
+            shot,
+            zero_distance,
+            sight_height,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("profile only holds plain data")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ProfileError> {
+        let profile: ShotProfile = serde_json::from_str(json).map_err(ProfileError::Malformed)?;
+        if profile.version != PROFILE_VERSION {
+            return Err(ProfileError::UnsupportedVersion(profile.version));
+        }
+        Ok(profile)
+    }
+
+    /// The whole setup as `key=value` pairs for a shareable URL. Lists, like
+    /// the wind profile, are written as `;`-separated rows of `,`-separated
+    /// numbers.
+    pub fn to_query(&self) -> String {
+        let shot = &self.shot;
+        let numbers = [
+            ("wind", shot.wind),
+            ("wind_dir", shot.wind_direction),
+            ("elevation", shot.elevation),
+            ("caliber", shot.caliber),
+            ("bc", shot.ballistic_coefficient),
+            ("mv", shot.muzzle_velocity),
+            ("mv_temp", shot.velocity_temp_sensitivity),
+            ("ref_temp", shot.reference_temperature),
+            ("mass", shot.mass),
+            ("twist", shot.twist_rate),
+            ("bullet_length", shot.bullet_length),
+            ("latitude", shot.latitude),
+            ("azimuth", shot.azimuth),
+            ("altitude", shot.altitude),
+            ("temperature", shot.temperature),
+            ("pressure", shot.pressure),
+            ("humidity", shot.humidity),
+            ("time_step", shot.time_step),
+            ("max_time", shot.max_flight_time),
+            ("max_range", shot.max_range),
+            ("zero", self.zero_distance),
+            ("sight_height", self.sight_height),
+        ];
+        let switches = [
+            ("air", shot.drag_enabled),
+            ("magnus", shot.magnus_enabled),
+            ("eotvos", shot.eotvos_enabled),
+        ];
+        let labels = [
+            ("drag", shot.drag_model.label()),
+            ("twist_dir", shot.twist_direction.label()),
+            ("integrator", shot.integrator.label()),
+        ];
+        let drag_table = match shot.drag_model {
+            DragModel::Custom(table) => table
+                .points()
+                .iter()
+                .map(|&(mach, cd)| vec![mach, cd])
+                .collect(),
+            _ => Vec::new(),
+        };
+        let lists = [
+            ("drag_table", drag_table),
+            (
+                "wind_profile",
+                shot.wind_profile
+                    .iter()
+                    .map(|sample| vec![sample.height, sample.speed, sample.direction])
+                    .collect(),
+            ),
+            (
+                "bc_bands",
+                shot.bc_bands
+                    .iter()
+                    .map(|band| vec![band.velocity, band.ballistic_coefficient])
+                    .collect(),
+            ),
+        ];
+
+        let numbers = numbers
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value));
+        let switches = switches.iter().map(|(key, on)| format!("{}={}", key, on));
+        let labels = labels
+            .iter()
+            .map(|(key, label)| format!("{}={}", key, label));
+        let lists = lists
+            .iter()
+            .filter(|(_, rows)| !rows.is_empty())
+            .map(|(key, rows)| format!("{}={}", key, join_rows(rows)));
+        numbers
+            .chain(switches)
+            .chain(labels)
+            .chain(lists)
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// Reads a query string written by [`ShotProfile::to_query`], with or
+    /// without its leading `?`. Unknown keys are ignored, and anything
+    /// missing or unreadable keeps its default.
+    pub fn from_query(query: &str) -> Self {
+        let mut profile = ShotProfile::default();
+        let mut drag_label = None;
+        let mut drag_table = None;
+        let pairs = query
+            .trim_start_matches('?')
+            .split('&')
+            .filter_map(|pair| pair.split_once('='));
+        for (key, value) in pairs {
+            let shot = &mut profile.shot;
+            match key {
+                "drag" => drag_label = Some(value),
+                "twist_dir" => {
+                    if let Ok(direction) = value.parse() {
+                        shot.twist_direction = direction;
+                    }
+                }
+                "integrator" => {
+                    if let Ok(integrator) = value.parse() {
+                        shot.integrator = integrator;
+                    }
+                }
+                "air" | "magnus" | "eotvos" => {
+                    let Ok(on) = value.parse() else {
+                        continue;
+                    };
+                    match key {
+                        "air" => shot.drag_enabled = on,
+                        "magnus" => shot.magnus_enabled = on,
+                        _ => shot.eotvos_enabled = on,
+                    }
+                }
+                "drag_table" => {
+                    let points = split_rows::<2>(value).map(|rows| {
+                        rows.iter()
+                            .map(|&[mach, cd]| (mach, cd))
+                            .collect::<Vec<_>>()
+                    });
+                    drag_table = points.and_then(|points| DragTable::new(&points).ok());
+                }
+                "wind_profile" => {
+                    if let Some(rows) = split_rows::<3>(value) {
+                        let samples: Vec<WindSample> = rows
+                            .iter()
+                            .map(|&[height, speed, direction]| WindSample {
+                                height,
+                                speed,
+                                direction,
+                            })
+                            .collect();
+                        shot.wind_profile = WindProfile::from_slice(&samples);
+                    }
+                }
+                "bc_bands" => {
+                    if let Some(rows) = split_rows::<2>(value) {
+                        let bands: Vec<BcBand> = rows
+                            .iter()
+                            .map(|&[velocity, ballistic_coefficient]| BcBand {
+                                velocity,
+                                ballistic_coefficient,
+                            })
+                            .collect();
+                        shot.bc_bands = BcBands::from_slice(&bands);
+                    }
+                }
+                _ => {
+                    let Ok(number) = value.parse::<f64>() else {
+                        continue;
+                    };
+                    let field = match key {
+                        "wind" => &mut shot.wind,
+                        "wind_dir" => &mut shot.wind_direction,
+                        "elevation" => &mut shot.elevation,
+                        "caliber" => &mut shot.caliber,
+                        "bc" => &mut shot.ballistic_coefficient,
+                        "mv" => &mut shot.muzzle_velocity,
+                        "mv_temp" => &mut shot.velocity_temp_sensitivity,
+                        "ref_temp" => &mut shot.reference_temperature,
+                        "mass" => &mut shot.mass,
+                        "twist" => &mut shot.twist_rate,
+                        "bullet_length" => &mut shot.bullet_length,
+                        "latitude" => &mut shot.latitude,
+                        "azimuth" => &mut shot.azimuth,
+                        "altitude" => &mut shot.altitude,
+                        "temperature" => &mut shot.temperature,
+                        "pressure" => &mut shot.pressure,
+                        "humidity" => &mut shot.humidity,
+                        "time_step" => &mut shot.time_step,
+                        "max_time" => &mut shot.max_flight_time,
+                        "max_range" => &mut shot.max_range,
+                        "zero" => &mut profile.zero_distance,
+                        "sight_height" => &mut profile.sight_height,
+                        _ => continue,
+                    };
+                    *field = number;
+                }
+            }
+        }
+        // The table may come before or after the model that names it.
+        match (drag_label, drag_table) {
+            (Some("Custom"), Some(table)) => profile.shot.drag_model = DragModel::Custom(table),
+            (Some(label), _) => {
+                if let Ok(model) = label.parse() {
+                    profile.shot.drag_model = model;
+                }
+            }
+            _ => {}
+        }
+        profile
+    }
+
+    /// Whether a link from [`ShotProfile::to_query`] brings back exactly this
+    /// profile. Not when a value can't be written as text, like NaN.
+    pub fn fits_in_query(&self) -> bool {
+        ShotProfile::from_query(&self.to_query()) == *self
+    }
+}
+
+fn join_rows(rows: &[Vec<f64>]) -> String {
+    rows.iter()
+        .map(|row| row.iter().map(f64::to_string).collect::<Vec<_>>().join(","))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Reads rows written by [`join_rows`], or `None` if any row isn't `N`
+/// numbers.
+fn split_rows<const N: usize>(value: &str) -> Option<Vec<[f64; N]>> {
+    value
+        .split(';')
+        .map(|row| {
+            let numbers: Vec<f64> = row
+                .split(',')
+                .map(|number| number.parse().ok())
+                .collect::<Option<_>>()?;
+            numbers.try_into().ok()
+        })
+        .collect()
+}
+
+/// Profiles saved under user-chosen names.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Presets(HashMap<String, ShotProfile>);
+
+impl Presets {
+    /// Saves `profile` as `name`, replacing any preset already called that.
+    pub fn save(&mut self, name: &str, profile: ShotProfile) {
+        self.0.insert(name.to_string(), profile);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ShotProfile> {
+        self.0.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<ShotProfile> {
+        self.0.get(name).copied()
+    }
+
+    /// Preset names in alphabetical order.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.0.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("presets only hold plain data")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ProfileError> {
+        let presets: Presets = serde_json::from_str(json).map_err(ProfileError::Malformed)?;
+        if let Some(profile) = presets.0.values().find(|p| p.version != PROFILE_VERSION) {
+            return Err(ProfileError::UnsupportedVersion(profile.version));
+        }
+        Ok(presets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ballistics::Integrator;
+    use crate::spin::TwistDirection;
+
+    #[test]
+    fn profile_round_trips_through_json() {
+        let shot = ShotParams {
+            wind: 3.5,
+            elevation: 0.12,
+            drag_model: DragModel::G7,
+            twist_direction: TwistDirection::Left,
+            altitude: 1500.0,
+            humidity: 40.0,
+            ..ShotParams::default()
+        };
+        let profile = ShotProfile::new(shot, 200.0, 0.05);
+        assert_eq!(ShotProfile::from_json(&profile.to_json()).unwrap(), profile);
+    }
+
+    #[test]
+    fn missing_fields_take_their_defaults() {
+        let profile = ShotProfile::from_json(r#"{"version": 1, "shot": {"wind": 2.0}}"#).unwrap();
+        assert_eq!(profile.shot.wind, 2.0);
+        assert_eq!(
+            profile.shot.muzzle_velocity,
+            ShotParams::default().muzzle_velocity
+        );
+        assert_eq!(profile.zero_distance, 100.0);
+    }
+
+    #[test]
+    fn default_matches_an_empty_profile() {
+        assert_eq!(
+            ShotProfile::from_json(r#"{"version": 1}"#).unwrap(),
+            ShotProfile::default()
+        );
+    }
+
+    #[test]
+    fn rejects_bad_version_and_malformed_input() {
+        assert!(matches!(
+            ShotProfile::from_json(r#"{"version": 99}"#),
+            Err(ProfileError::UnsupportedVersion(99))
+        ));
+        assert!(matches!(
+            ShotProfile::from_json("{}"),
+            Err(ProfileError::Malformed(_))
+        ));
+        assert!(matches!(
+            ShotProfile::from_json("not json"),
+            Err(ProfileError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn presets_add_overwrite_and_remove() {
+        let mut presets = Presets::default();
+        presets.save("match", ShotProfile::default());
+        presets.save(
+            "hunting",
+            ShotProfile::new(ShotParams::default(), 200.0, 0.04),
+        );
+        assert_eq!(presets.names(), ["hunting", "match"]);
+
+        let overwritten = ShotProfile::new(ShotParams::default(), 300.0, 0.05);
+        presets.save("match", overwritten);
+        assert_eq!(presets.names().len(), 2);
+        assert_eq!(presets.get("match"), Some(overwritten));
+
+        assert_eq!(presets.remove("match"), Some(overwritten));
+        assert_eq!(presets.remove("match"), None);
+        assert_eq!(presets.names(), ["hunting"]);
+    }
+
+    #[test]
+    fn presets_round_trip_through_json() {
+        let mut presets = Presets::default();
+        presets.save("a", ShotProfile::new(ShotParams::default(), 150.0, 0.03));
+        assert_eq!(Presets::from_json(&presets.to_json()).unwrap(), presets);
+        assert!(matches!(
+            Presets::from_json(r#"{"old": {"version": 0}}"#),
+            Err(ProfileError::UnsupportedVersion(0))
+        ));
+    }
+
+    #[test]
+    fn query_round_trips_the_shared_fields() {
+        let shot = ShotParams {
+            wind: 3.5,
+            elevation: 0.1234567,
+            muzzle_velocity: 792.0,
+            drag_model: DragModel::G7,
+            ..ShotParams::default()
+        };
+        let profile = ShotProfile::new(shot, 200.0, 0.045);
+        assert_eq!(ShotProfile::from_query(&profile.to_query()), profile);
+        assert!(profile.fits_in_query());
+    }
+
+    #[test]
+    fn query_round_trips_everything_else_too() {
+        let table = DragTable::new(&[(0.0, 0.21), (1.0, 0.38), (2.5, 0.295)]).unwrap();
+        let shot = ShotParams {
+            drag_model: DragModel::Custom(table),
+            twist_direction: TwistDirection::Left,
+            integrator: Integrator::Euler,
+            wind_profile: WindProfile::from_slice(&[
+                WindSample {
+                    height: 0.0,
+                    speed: 2.0,
+                    direction: 90.0,
+                },
+                WindSample {
+                    height: 30.0,
+                    speed: 5.5,
+                    direction: 120.0,
+                },
+            ]),
+            bc_bands: BcBands::from_slice(&[
+                BcBand {
+                    velocity: 800.0,
+                    ballistic_coefficient: 0.47,
+                },
+                BcBand {
+                    velocity: 0.0,
+                    ballistic_coefficient: 0.44,
+                },
+            ]),
+            velocity_temp_sensitivity: 0.7,
+            bullet_length: 0.0312,
+            drag_enabled: false,
+            magnus_enabled: true,
+            eotvos_enabled: false,
+            time_step: 0.002,
+            max_flight_time: 30.0,
+            max_range: 2500.0,
+            ..ShotParams::default()
+        };
+        let profile = ShotProfile::new(shot, 300.0, 0.05);
+        assert_eq!(ShotProfile::from_query(&profile.to_query()), profile);
+
+        let unwritable = ShotProfile::new(
+            ShotParams {
+                wind: f64::NAN,
+                ..ShotParams::default()
+            },
+            100.0,
+            0.0,
+        );
+        assert!(!unwritable.fits_in_query());
+    }
+
+    #[test]
+    fn bad_query_values_keep_their_defaults() {
+        let profile = ShotProfile::from_query("?mv=900&wind=fast&unknown=1&bc&drag=G9");
+        assert_eq!(profile.shot.muzzle_velocity, 900.0);
+        assert_eq!(profile.shot.wind, ShotParams::default().wind);
+        assert_eq!(
+            profile.shot.ballistic_coefficient,
+            ShotParams::default().ballistic_coefficient
+        );
+        assert_eq!(profile.shot.drag_model, DragModel::default());
+        assert_eq!(ShotProfile::from_query(""), ShotProfile::default());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::units::AngleUnit;
+
+/// MOA in one radian, i.e. `60 * 180 / π`.
+pub const MOA_PER_RADIAN: f64 = 3437.75;
+/// Milliradians in one radian.
+pub const MIL_PER_RADIAN: f64 = 1000.0;
+
+/// An angular hold or dial-up for a linear offset at some range.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Correction {
+    pub moa: f64,
+    pub mil: f64,
+}
+
+/// Angular size of `offset` seen from `range`, both in the same units.
+pub fn correction(offset: f64, range: f64) -> Correction {
+    if range <= 0.0 {
+        return Correction::default();
+    }
+    Correction {
+        moa: offset / range * MOA_PER_RADIAN,
+        mil: offset / range * MIL_PER_RADIAN,
+    }
+}
+
+/// Drop relative to the line of sight for a target `look_angle` degrees above
+/// (positive) or below (negative) the horizontal, by the rifleman's rule:
+/// only gravity's component across the line of sight bends the shot away
+/// from it, so uphill and downhill shots both drop less than on the flat.
+pub fn inclined_drop(flat_drop: f64, look_angle: f64) -> f64 {
+    flat_drop * look_angle.to_radians().cos()
+}
+
+/// A correction rounded to whole turret clicks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Clicks {
+    /// Signed click count, in the same direction as the correction.
+    pub count: i64,
+    /// What the rounded clicks leave uncorrected, in the click unit.
+    pub residual: f64,
+}
+
+/// Rounds `angle`, given in `angle_unit`, to the nearest click of a turret
+/// that moves `click_value` of `click_unit` per click. `None` unless the
+/// click value is positive.
+pub fn clicks(
+    angle: f64,
+    angle_unit: AngleUnit,
+    click_value: f64,
+    click_unit: AngleUnit,
+) -> Option<Clicks> {
+    if click_value <= 0.0 || click_value.is_nan() {
+        return None;
+    }
+    let angle = click_unit.from_radians(angle_unit.to_radians(angle));
+    let count = (angle / click_value).round();
+    Some(Clicks {
+        count: count as i64,
+        residual: angle - count * click_value,
+    })
+}
+
+/// How far a target moving at `target_speed` travels across the line of fire
+/// during `time_of_flight`. `target_angle` is the target's heading in degrees
+/// relative to the line of fire, so 90° is a full crossing target.
+pub fn lead(target_speed: f64, target_angle: f64, time_of_flight: f64) -> f64 {
+    target_speed * target_angle.to_radians().sin() * time_of_flight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_moa_at_100_yards_is_about_an_inch() {
+        let inches = 1.047;
+        let yards = 100.0;
+        let moa = correction(inches, yards * 36.0).moa;
+        assert!((moa - 1.0).abs() < 1e-3, "{moa}");
+    }
+
+    #[test]
+    fn one_mil_is_one_meter_per_kilometer() {
+        let mil = correction(1.0, 1000.0).mil;
+        assert!((mil - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn crossing_target_needs_speed_times_flight_time() {
+        assert!((lead(10.0, 90.0, 1.0) - 10.0).abs() < 1e-12);
+        assert!((lead(10.0, 30.0, 1.0) - 5.0).abs() < 1e-12);
+        assert!(lead(10.0, 0.0, 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn slopes_reduce_drop_either_way() {
+        let flat = 1.2;
+        let uphill = inclined_drop(flat, 30.0);
+        assert!(uphill < flat);
+        assert!((uphill - flat * 3f64.sqrt() / 2.0).abs() < 1e-12);
+        assert_eq!(inclined_drop(flat, -30.0), uphill);
+        assert_eq!(inclined_drop(flat, 0.0), flat);
+    }
+
+    #[test]
+    fn four_moa_is_sixteen_quarter_moa_clicks() {
+        let result = clicks(4.0, AngleUnit::Moa, 0.25, AngleUnit::Moa).unwrap();
+        assert_eq!(result.count, 16);
+        assert!(result.residual.abs() < 1e-12);
+    }
+
+    #[test]
+    fn converts_to_the_click_unit_before_rounding() {
+        // 1 MIL is about 3.44 MOA: 14 quarter-MOA clicks, 0.06 MOA short.
+        let result = clicks(1.0, AngleUnit::Mil, 0.25, AngleUnit::Moa).unwrap();
+        assert_eq!(result.count, 14);
+        assert!(
+            (result.residual + 0.0623).abs() < 1e-3,
+            "{}",
+            result.residual
+        );
+        assert_eq!(
+            clicks(-0.37, AngleUnit::Mil, 0.1, AngleUnit::Mil)
+                .unwrap()
+                .count,
+            -4
+        );
+        assert_eq!(clicks(1.0, AngleUnit::Mil, 0.0, AngleUnit::Mil), None);
+    }
+
+    #[test]
+    fn zero_range_has_no_correction() {
+        assert_eq!(correction(1.0, 0.0), Correction::default());
+    }
+}
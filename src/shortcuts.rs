@@ -0,0 +1,76 @@
+//! Keyboard shortcuts for the calculator's main buttons.
+
+/// What a shortcut does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shortcut {
+    Fire,
+    TogglePause,
+    Reset,
+}
+
+/// One key and what it's bound to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Binding {
+    /// A `KeyboardEvent.key` value, matched ignoring case so caps lock
+    /// doesn't get in the way.
+    pub key: &'static str,
+    /// How the key and what it does are written in the list shown under
+    /// the controls.
+    pub label: &'static str,
+    pub description: &'static str,
+    pub shortcut: Shortcut,
+}
+
+pub const BINDINGS: [Binding; 3] = [
+    Binding {
+        key: "Enter",
+        label: "Enter",
+        description: "fires",
+        shortcut: Shortcut::Fire,
+    },
+    Binding {
+        key: " ",
+        label: "Space",
+        description: "pauses or resumes",
+        shortcut: Shortcut::TogglePause,
+    },
+    Binding {
+        key: "r",
+        label: "R",
+        description: "resets",
+        shortcut: Shortcut::Reset,
+    },
+];
+
+/// The shortcut bound to `key`, unless it was pressed in a form field,
+/// where it's left to type or submit as usual.
+pub fn shortcut(key: &str, in_field: bool) -> Option<Shortcut> {
+    if in_field {
+        return None;
+    }
+    BINDINGS
+        .iter()
+        .find(|binding| binding.key.eq_ignore_ascii_case(key))
+        .map(|binding| binding.shortcut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_key_triggers_its_shortcut() {
+        assert_eq!(shortcut("Enter", false), Some(Shortcut::Fire));
+        assert_eq!(shortcut(" ", false), Some(Shortcut::TogglePause));
+        assert_eq!(shortcut("r", false), Some(Shortcut::Reset));
+        assert_eq!(shortcut("R", false), Some(Shortcut::Reset));
+        assert_eq!(shortcut("x", false), None);
+    }
+
+    #[test]
+    fn keys_typed_into_fields_are_left_alone() {
+        for binding in BINDINGS {
+            assert_eq!(shortcut(binding.key, true), None);
+        }
+    }
+}
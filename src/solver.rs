@@ -0,0 +1,518 @@
+use crate::ballistics::{
+    height_above_sight_line, landing_point, point_at_range, simulate, simulate_to_range,
+    ShotParams, TrajectoryPoint,
+};
+use crate::error::BallisticError;
+use crate::validation;
+
+/// Acceptable miss at the target, in meters.
+pub const TOLERANCE: f64 = 0.001;
+/// Acceptable miss at the zero. The sight line and a flat trajectory cross
+/// at a shallow angle, so a millimeter there moves the far zero by meters.
+pub const ZERO_TOLERANCE: f64 = 0.00001;
+pub const MAX_ITERATIONS: usize = 100;
+/// How closely the range-maximizing launch angle is pinned down, in degrees.
+pub const ANGLE_TOLERANCE: f64 = 0.01;
+/// How far out to follow a point-blank trajectory looking for its exit.
+pub const MPBR_SEARCH_RANGE: f64 = 3000.0;
+
+/// Height of the trajectory when it reaches `distance` at the given launch
+/// angle, or negative infinity if the shot falls short.
+fn height_at(params: &ShotParams, elevation: f64, distance: f64) -> f64 {
+    simulate(&ShotParams {
+        elevation,
+        ..*params
+    })
+    .ok()
+    .and_then(|points| point_at_range(&points, distance))
+    .map_or(f64::NEG_INFINITY, |point| point.position.y)
+}
+
+/// Step in degrees used to estimate how height changes with elevation.
+const DERIVATIVE_STEP: f64 = 1e-4;
+
+/// Searches launch angles between 0° and 45° until `height_at` is within
+/// `tolerance` of `target_height`, assuming height grows with elevation over
+/// that span. Returns the elevation and the iterations it took.
+///
+/// Takes Newton steps on a numerical derivative, which converge quickly once
+/// close, and bisects instead whenever the derivative is unusable or a step
+/// would leave the bracket. Gives up with `NonConvergent` after
+/// `MAX_ITERATIONS`, or `OutOfRange` if even 45° falls short.
+fn search_elevation(
+    height_at: impl Fn(f64) -> f64,
+    target_height: f64,
+    tolerance: f64,
+) -> Result<(f64, usize), BallisticError> {
+    let (mut low, mut high) = (0.0, 45.0);
+    if height_at(high) < target_height {
+        return Err(BallisticError::OutOfRange);
+    }
+
+    let mut elevation = (low + high) / 2.0;
+    for iteration in 1..=MAX_ITERATIONS {
+        let miss = height_at(elevation) - target_height;
+        if miss.abs() <= tolerance {
+            return Ok((elevation, iteration));
+        }
+        if miss < 0.0 {
+            low = elevation;
+        } else {
+            high = elevation;
+        }
+
+        let slope =
+            (height_at(elevation + DERIVATIVE_STEP) - target_height - miss) / DERIVATIVE_STEP;
+        let newton = elevation - miss / slope;
+        elevation = if slope.abs() > f64::EPSILON && newton > low && newton < high {
+            newton
+        } else {
+            (low + high) / 2.0
+        };
+    }
+
+    Err(BallisticError::NonConvergent)
+}
+
+fn find_elevation(
+    height_at: impl Fn(f64) -> f64,
+    target_height: f64,
+    tolerance: f64,
+) -> Result<f64, BallisticError> {
+    search_elevation(height_at, target_height, tolerance).map(|(elevation, _)| elevation)
+}
+
+/// Finds the low-angle launch elevation in degrees that passes through the
+/// point `target_distance` downrange and `target_height` above the muzzle.
+///
+/// Searches between a flat and a 45° shot, so fails with `OutOfRange` when
+/// even the steepest of those falls below the target, and with whatever
+/// [`validation::check`] finds if `params` can't be simulated.
+pub fn solve_elevation(
+    params: &ShotParams,
+    target_distance: f64,
+    target_height: f64,
+) -> Result<f64, BallisticError> {
+    validation::check(params)?;
+    find_elevation(
+        |elevation| height_at(params, elevation, target_distance),
+        target_height,
+        TOLERANCE,
+    )
+}
+
+/// A zeroed rifle: the bore angle and where the bullet crosses the sight line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Zero {
+    /// Bore elevation in degrees relative to a level line of sight.
+    pub elevation: f64,
+    /// Where the rising bullet first crosses the line of sight.
+    pub near_zero: f64,
+    /// Where the falling bullet crosses back through it, if it does before
+    /// the search gives up.
+    pub far_zero: Option<f64>,
+}
+
+/// Zeroes the rifle so the bullet is on the line of sight `sight_height`
+/// above the bore at `zero_distance` meters.
+pub fn zero(params: &ShotParams, zero_distance: f64, sight_height: f64) -> Option<Zero> {
+    let elevation = find_elevation(
+        |elevation| {
+            let points = simulate_to_range(
+                &ShotParams {
+                    elevation,
+                    ..*params
+                },
+                zero_distance,
+            );
+            point_at_range(&points, zero_distance).map_or(f64::NEG_INFINITY, |p| p.position.y)
+        },
+        sight_height,
+        ZERO_TOLERANCE,
+    )
+    .ok()?;
+
+    // Look well past the zero so a short zero still finds its far crossing.
+    // The zero distance itself is one of the two crossings.
+    let points = simulate_to_range(
+        &ShotParams {
+            elevation,
+            ..*params
+        },
+        zero_distance * 20.0,
+    );
+    let mut crossings = crossings(&points, |point| {
+        height_above_sight_line(point, sight_height)
+    });
+
+    let near_zero = crossings.next()?;
+    let far_zero = crossings.next();
+    Some(Zero {
+        elevation,
+        near_zero,
+        far_zero,
+    })
+}
+
+/// Ranges at which `height` changes sign, interpolated within each step.
+fn crossings<'a>(
+    points: &'a [TrajectoryPoint],
+    height: impl Fn(&TrajectoryPoint) -> f64 + 'a,
+) -> impl Iterator<Item = f64> + 'a {
+    points.windows(2).filter_map(move |pair| {
+        let before = height(&pair[0]);
+        let after = height(&pair[1]);
+        if before.signum() == after.signum() || before == after {
+            return None;
+        }
+        let t = before / (before - after);
+        Some(pair[0].position.x + (pair[1].position.x - pair[0].position.x) * t)
+    })
+}
+
+/// Maximum point-blank range: the zero that keeps the whole arc within a
+/// vital zone, and how far that zone extends.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mpbr {
+    /// Bore elevation in degrees.
+    pub elevation: f64,
+    /// Far zero of that elevation, the distance to zero the rifle at.
+    pub zero_distance: f64,
+    /// Where the bullet drops `vital_radius` below the line of sight.
+    pub max_range: f64,
+}
+
+/// Finds the zero whose peak rise above the line of sight is exactly
+/// `vital_radius`, so the bullet stays within ±`vital_radius` for as far as
+/// possible. The muzzle itself sits below the sight line and is ignored.
+pub fn mpbr(params: &ShotParams, vital_radius: f64, sight_height: f64) -> Option<Mpbr> {
+    let trajectory = |elevation| {
+        simulate_to_range(
+            &ShotParams {
+                elevation,
+                ..*params
+            },
+            MPBR_SEARCH_RANGE,
+        )
+    };
+    let peak_rise = |elevation| {
+        trajectory(elevation)
+            .iter()
+            .map(|point| height_above_sight_line(point, sight_height))
+            .fold(f64::NEG_INFINITY, f64::max)
+    };
+    let elevation = find_elevation(peak_rise, vital_radius, TOLERANCE).ok()?;
+
+    let points = trajectory(elevation);
+    let apex = points
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.position.y.total_cmp(&b.position.y))
+        .map(|(index, _)| index)?;
+    let descent = &points[apex..];
+    let zero_distance = crossings(descent, |point| {
+        height_above_sight_line(point, sight_height)
+    })
+    .next()?;
+    let max_range = crossings(descent, |point| {
+        height_above_sight_line(point, sight_height) + vital_radius
+    })
+    .next()?;
+
+    Some(Mpbr {
+        elevation,
+        zero_distance,
+        max_range,
+    })
+}
+
+/// The stretch of range around a target over which the shot would still hit
+/// it, for a target standing on the line of sight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DangerSpace {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl DangerSpace {
+    pub fn length(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Finds where around `range` the shot stays between the line of sight and
+/// `target_height` above it, interpolating where it enters and leaves that
+/// band. `None` if the shot isn't within the band at `range` at all.
+pub fn danger_space(
+    points: &[TrajectoryPoint],
+    range: f64,
+    target_height: f64,
+    sight_height: f64,
+) -> Option<DangerSpace> {
+    let height = |point: &TrajectoryPoint| height_above_sight_line(point, sight_height);
+    let inside = |point: &TrajectoryPoint| (0.0..=target_height).contains(&height(point));
+    if !inside(&point_at_range(points, range)?) {
+        return None;
+    }
+
+    // Where the step from `a` (inside) to `b` (outside) crosses the band edge.
+    let edge = |a: &TrajectoryPoint, b: &TrajectoryPoint| {
+        let limit = if height(b) < 0.0 { 0.0 } else { target_height };
+        let t = (limit - height(a)) / (height(b) - height(a));
+        a.position.x + (b.position.x - a.position.x) * t
+    };
+    let after = points
+        .iter()
+        .position(|point| point.position.x > range)
+        .unwrap_or(points.len());
+    let start = points[..after]
+        .windows(2)
+        .rev()
+        .find(|pair| !inside(&pair[0]))
+        .map_or(points[0].position.x, |pair| edge(&pair[1], &pair[0]));
+    let end = points[after.saturating_sub(1)..]
+        .windows(2)
+        .find(|pair| !inside(&pair[1]))
+        .map_or(points[points.len() - 1].position.x, |pair| {
+            edge(&pair[0], &pair[1])
+        });
+    Some(DangerSpace { start, end })
+}
+
+/// The launch angle that carries a shot furthest over level ground.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MaxRange {
+    /// Launch elevation in degrees.
+    pub elevation: f64,
+    /// Where that shot lands, in meters.
+    pub range: f64,
+}
+
+/// Golden-section searches launch angles from 1° to 89° for the longest
+/// range. Drag pulls the answer below the vacuum 45°, so it has to be found
+/// by simulating. The caps on flight time and range are lifted while
+/// searching; fails with [`BallisticError::OutOfRange`] only if a shot still
+/// hasn't landed after [`MAX_STEPS`](crate::ballistics::MAX_STEPS).
+pub fn optimal_angle(params: &ShotParams) -> Result<MaxRange, BallisticError> {
+    let params = params.uncapped();
+    let range = |elevation| {
+        let points = simulate(&ShotParams {
+            elevation,
+            ..params
+        })?;
+        landing_point(&points)
+            .map(|point| point.position.x)
+            .ok_or(BallisticError::OutOfRange)
+    };
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+
+    let (mut low, mut high) = (1.0, 89.0);
+    let mut a = high - ratio * (high - low);
+    let mut b = low + ratio * (high - low);
+    let (mut range_a, mut range_b) = (range(a)?, range(b)?);
+    while high - low > ANGLE_TOLERANCE {
+        if range_a < range_b {
+            low = a;
+            (a, range_a) = (b, range_b);
+            b = low + ratio * (high - low);
+            range_b = range(b)?;
+        } else {
+            high = b;
+            (b, range_b) = (a, range_a);
+            a = high - ratio * (high - low);
+            range_a = range(a)?;
+        }
+    }
+
+    let elevation = (low + high) / 2.0;
+    Ok(MaxRange {
+        elevation,
+        range: range(elevation)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_for_a_reachable_target() {
+        let params = ShotParams::default();
+        let elevation = solve_elevation(&params, 300.0, 0.0).unwrap();
+        assert!(elevation > 0.0 && elevation < 1.0, "{elevation}");
+
+        let height = height_at(&params, elevation, 300.0);
+        assert!(height.abs() <= TOLERANCE, "{height}");
+    }
+
+    #[test]
+    fn converges_on_a_600m_target_quickly() {
+        let params = ShotParams::default();
+        let (elevation, iterations) =
+            search_elevation(|elevation| height_at(&params, elevation, 600.0), 0.0, 0.01).unwrap();
+        assert!(iterations < 30, "{iterations}");
+        assert!(height_at(&params, elevation, 600.0).abs() <= 0.01);
+    }
+
+    #[test]
+    fn unreachable_target_has_no_solution() {
+        let params = ShotParams {
+            muzzle_velocity: 100.0,
+            ..ShotParams::default()
+        };
+        assert_eq!(
+            solve_elevation(&params, 5000.0, 0.0),
+            Err(BallisticError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn a_target_it_cannot_settle_on_is_non_convergent() {
+        // A step in height with the target partway up it never gets hit.
+        let step = |elevation: f64| if elevation < 10.0 { 0.0 } else { 1.0 };
+        assert_eq!(
+            search_elevation(step, 0.5, 0.0),
+            Err(BallisticError::NonConvergent)
+        );
+    }
+
+    #[test]
+    fn invalid_shots_are_rejected_before_solving() {
+        let params = ShotParams {
+            caliber: 0.0,
+            ..ShotParams::default()
+        };
+        assert_eq!(
+            solve_elevation(&params, 300.0, 0.0),
+            Err(BallisticError::InvalidCaliber)
+        );
+    }
+
+    #[test]
+    fn hundred_meter_zero_crosses_twice() {
+        let params = ShotParams::default();
+        let zeroed = zero(&params, 100.0, 0.05).unwrap();
+
+        // A fast, flat load barely rises above a 5 cm sight line, so the
+        // near crossing comes late.
+        assert!(
+            zeroed.near_zero > 40.0 && zeroed.near_zero < 80.0,
+            "{zeroed:?}"
+        );
+        let far_zero = zeroed.far_zero.unwrap();
+        assert!((far_zero - 100.0).abs() < 0.5, "{zeroed:?}");
+
+        let points = simulate_to_range(
+            &ShotParams {
+                elevation: zeroed.elevation,
+                ..params
+            },
+            100.0,
+        );
+        let rise = point_at_range(&points, (zeroed.near_zero + far_zero) / 2.0)
+            .map(|point| height_above_sight_line(&point, 0.05))
+            .unwrap();
+        assert!(rise > 0.0 && rise < 0.03, "{rise}");
+    }
+
+    #[test]
+    fn twenty_five_yard_zero_recrosses_a_few_hundred_yards_out() {
+        const YARD: f64 = 0.9144;
+        let zeroed = zero(&ShotParams::default(), 25.0 * YARD, 0.038).unwrap();
+        assert!((zeroed.near_zero / YARD - 25.0).abs() < 0.5, "{zeroed:?}");
+        let far_zero = zeroed.far_zero.unwrap() / YARD;
+        assert!(far_zero > 180.0 && far_zero < 280.0, "{far_zero}");
+    }
+
+    #[test]
+    fn a_shot_that_never_falls_back_has_no_far_zero() {
+        // The search only follows the shot 20 zero distances out, and this
+        // fast, drag-free one is still rising there.
+        let params = ShotParams {
+            drag_enabled: false,
+            muzzle_velocity: 3000.0,
+            ..ShotParams::default()
+        };
+        let zeroed = zero(&params, 10.0, 0.05).unwrap();
+        assert_eq!(zeroed.far_zero, None, "{zeroed:?}");
+    }
+
+    #[test]
+    fn flatter_shots_have_more_danger_space() {
+        let danger = |muzzle_velocity| {
+            let params = ShotParams {
+                muzzle_velocity,
+                ..ShotParams::default()
+            };
+            // Aim at the middle of a 1.8 m target 300 m away.
+            let elevation = solve_elevation(&params, 300.0, 0.05 + 0.9).unwrap();
+            let points = simulate_to_range(
+                &ShotParams {
+                    elevation,
+                    ..params
+                },
+                1000.0,
+            );
+            danger_space(&points, 300.0, 1.8, 0.05).unwrap()
+        };
+        let fast = danger(900.0);
+        let slow = danger(500.0);
+        assert!(fast.start < 300.0 && fast.end > 300.0, "{fast:?}");
+        assert!(fast.length() > slow.length(), "{fast:?} {slow:?}");
+    }
+
+    #[test]
+    fn no_danger_space_when_the_shot_misses() {
+        let points = simulate_to_range(&ShotParams::default(), 500.0);
+        assert_eq!(danger_space(&points, 400.0, 1.8, 0.05), None);
+    }
+
+    #[test]
+    fn flat_cartridges_have_a_longer_point_blank_range() {
+        let point_blank = |muzzle_velocity| {
+            let params = ShotParams {
+                muzzle_velocity,
+                ..ShotParams::default()
+            };
+            mpbr(&params, 0.1, 0.04).unwrap()
+        };
+        let fast = point_blank(900.0);
+        let slow = point_blank(450.0);
+
+        assert!(fast.zero_distance < fast.max_range, "{fast:?}");
+        assert!(slow.zero_distance < slow.max_range, "{slow:?}");
+        assert!(fast.max_range > 1.5 * slow.max_range, "{fast:?} {slow:?}");
+    }
+
+    #[test]
+    fn vacuum_range_peaks_at_45_degrees() {
+        let params = ShotParams {
+            drag_enabled: false,
+            muzzle_velocity: 100.0,
+            ..ShotParams::default()
+        };
+        let best = optimal_angle(&params).unwrap();
+        assert!((best.elevation - 45.0).abs() < 0.1, "{best:?}");
+        let expected = 100.0f64.powi(2) / crate::ballistics::GRAVITY;
+        assert!((best.range - expected).abs() < 1.0, "{best:?}");
+    }
+
+    #[test]
+    fn vacuum_range_is_not_cut_short_by_the_caps() {
+        // About 74 km and two minutes in the air, past both default caps.
+        let params = ShotParams {
+            drag_enabled: false,
+            ..ShotParams::default()
+        };
+        let best = optimal_angle(&params).unwrap();
+        assert!((best.elevation - 45.0).abs() < 0.1, "{best:?}");
+        let expected = params.muzzle_velocity.powi(2) / crate::ballistics::GRAVITY;
+        assert!((best.range - expected).abs() < 1e-3 * expected, "{best:?}");
+    }
+
+    #[test]
+    fn drag_lowers_the_best_angle() {
+        let best = optimal_angle(&ShotParams::default()).unwrap();
+        assert!(best.elevation < 45.0, "{best:?}");
+    }
+}
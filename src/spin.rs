@@ -0,0 +1,206 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::atmosphere::{air_density, STANDARD_PRESSURE, STANDARD_TEMPERATURE};
+use crate::units::AngleUnit;
+
+const METERS_PER_INCH: f64 = 0.0254;
+const GRAINS_PER_KILOGRAM: f64 = 15_432.358;
+const METERS_PER_SECOND_PER_MPH: f64 = 0.44704;
+const METERS_PER_FOOT: f64 = 0.3048;
+/// Muzzle velocity in m/s that Miller's formula assumes, 2800 fps.
+const MILLER_VELOCITY: f64 = 2800.0 * METERS_PER_FOOT;
+/// Sg below this is marginal: the bullet flies, but yaws more than it should.
+pub const MARGINAL_STABILITY: f64 = 1.4;
+/// Sg below this tumbles.
+pub const UNSTABLE_STABILITY: f64 = 1.0;
+/// Fraction of its spin a rifle bullet loses each second, give or take; air
+/// friction on the jacket slows it far less than drag slows its flight.
+pub const SPIN_DECAY_RATE: f64 = 0.04;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TwistDirection {
+    #[default]
+    Right,
+    Left,
+}
+
+impl TwistDirection {
+    pub const ALL: [TwistDirection; 2] = [TwistDirection::Right, TwistDirection::Left];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TwistDirection::Right => "Right",
+            TwistDirection::Left => "Left",
+        }
+    }
+
+    /// Sign of the lateral deflection, with positive to the shooter's right.
+    pub fn sign(self) -> f64 {
+        match self {
+            TwistDirection::Right => 1.0,
+            TwistDirection::Left => -1.0,
+        }
+    }
+}
+
+impl FromStr for TwistDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TwistDirection::ALL
+            .into_iter()
+            .find(|direction| direction.label() == s)
+            .ok_or(())
+    }
+}
+
+/// Miller gyroscopic stability factor.
+///
+/// `mass` is in kg, `caliber` and `length` in meters and `twist_rate` in
+/// inches per turn. The formula itself works in grains, inches and calibers.
+pub fn miller_stability(mass: f64, caliber: f64, length: f64, twist_rate: f64) -> f64 {
+    let mass = mass * GRAINS_PER_KILOGRAM;
+    let diameter = caliber / METERS_PER_INCH;
+    let twist = twist_rate / diameter;
+    let length = length / caliber;
+
+    30.0 * mass / (twist.powi(2) * diameter.powi(3) * length * (1.0 + length.powi(2)))
+}
+
+/// Miller's adjustment of `stability` for a `velocity` m/s launch through air
+/// of `density` kg/m³, against the 2800 fps and ICAO sea-level conditions the
+/// bare formula assumes. Faster bullets and thinner air are both more stable.
+pub fn corrected_stability(stability: f64, velocity: f64, density: f64) -> f64 {
+    let standard_density = air_density(0.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+    stability * (velocity / MILLER_VELOCITY).cbrt() * standard_density / density
+}
+
+/// Spin rate in rpm leaving a barrel with a `twist_rate` inch per turn twist
+/// at `muzzle_velocity` m/s, by `fps * 720 / twist`. Zero for no twist.
+pub fn muzzle_spin_rate(muzzle_velocity: f64, twist_rate: f64) -> f64 {
+    if twist_rate <= 0.0 {
+        return 0.0;
+    }
+    muzzle_velocity / METERS_PER_FOOT * 720.0 / twist_rate
+}
+
+/// Spin rate in rpm `time` seconds into the flight, decaying exponentially
+/// from `muzzle_rpm` at [`SPIN_DECAY_RATE`].
+pub fn spin_rate(muzzle_rpm: f64, time: f64) -> f64 {
+    muzzle_rpm * (-SPIN_DECAY_RATE * time).exp()
+}
+
+/// Spin drift in meters after `time_of_flight` seconds, using the
+/// `1.25 * (Sg + 1.2) * tof^1.83` inch approximation.
+pub fn spin_drift(stability: f64, time_of_flight: f64, direction: TwistDirection) -> f64 {
+    let drift = 1.25 * (stability + 1.2) * time_of_flight.powf(1.83);
+    direction.sign() * drift * METERS_PER_INCH
+}
+
+/// Vertical throw in radians from aerodynamic jump, using Litz's
+/// `0.01 Sg - 0.0024 L + 0.032` MOA per mph of crosswind with `L` the
+/// bullet length in calibers.
+///
+/// `crosswind` is the air's velocity to the shooter's right in m/s. With a
+/// right-hand twist, wind from the left throws the shot up.
+pub fn aerodynamic_jump(
+    stability: f64,
+    caliber: f64,
+    length: f64,
+    crosswind: f64,
+    direction: TwistDirection,
+) -> f64 {
+    let per_mph = 0.01 * stability - 0.0024 * length / caliber + 0.032;
+    let jump = per_mph * crosswind / METERS_PER_SECOND_PER_MPH;
+    direction.sign() * AngleUnit::Moa.to_radians(jump)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn left_twist_drifts_opposite_to_right_twist() {
+        let right = spin_drift(1.8, 1.2, TwistDirection::Right);
+        let left = spin_drift(1.8, 1.2, TwistDirection::Left);
+        assert!(right > 0.0);
+        assert_eq!(left, -right);
+    }
+
+    #[test]
+    fn drift_grows_with_time_of_flight() {
+        let short = spin_drift(1.8, 0.5, TwistDirection::Right);
+        let long = spin_drift(1.8, 1.5, TwistDirection::Right);
+        assert!(long > short);
+        assert_eq!(spin_drift(1.8, 0.0, TwistDirection::Right), 0.0);
+    }
+
+    #[test]
+    fn reversing_the_crosswind_flips_the_jump() {
+        let jump =
+            |crosswind| aerodynamic_jump(1.8, 0.00782, 0.0309, crosswind, TwistDirection::Right);
+        // Wind from the left, blowing right, throws a right-hand twist up.
+        assert!(jump(4.0) > 0.0);
+        assert_eq!(jump(-4.0), -jump(4.0));
+        assert_eq!(jump(0.0), 0.0);
+        // A 10 mph wind moves a typical .308 about 0.4 MOA.
+        let moa = AngleUnit::Moa.from_radians(jump(10.0 * METERS_PER_SECOND_PER_MPH));
+        assert!((0.3..0.5).contains(&moa), "{moa}");
+    }
+
+    #[test]
+    fn faster_twist_spins_faster() {
+        let slow = muzzle_spin_rate(2700.0 * METERS_PER_FOOT, 12.0);
+        let fast = muzzle_spin_rate(2700.0 * METERS_PER_FOOT, 8.0);
+        assert!((slow - 162_000.0).abs() < 1e-6, "{slow}");
+        assert!(fast > slow);
+        assert_eq!(muzzle_spin_rate(800.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn spin_decays_slowly_over_the_flight() {
+        assert_eq!(spin_rate(160_000.0, 0.0), 160_000.0);
+        let later = spin_rate(160_000.0, 1.5);
+        assert!(later < 160_000.0 && later > 150_000.0, "{later}");
+    }
+
+    #[test]
+    fn stability_is_plausible_for_308_168gr() {
+        // 168 gr, 1.215" long .308 from a 1:12" barrel sits around 1.8.
+        let sg = miller_stability(
+            168.0 / GRAINS_PER_KILOGRAM,
+            0.308 * 0.0254,
+            1.215 * 0.0254,
+            12.0,
+        );
+        assert!((1.6..2.0).contains(&sg), "{sg}");
+    }
+
+    #[test]
+    fn reproduces_a_worked_180gr_308_example() {
+        // 180 gr .308, 1.340" long, 1:12" twist: t = 38.96 and l = 4.351
+        // calibers, so Sg = 30 * 180 / (38.96² * 0.308³ * 4.351 * 19.93) = 1.40,
+        // right on the marginal line.
+        let sg = miller_stability(
+            180.0 / GRAINS_PER_KILOGRAM,
+            0.308 * METERS_PER_INCH,
+            1.340 * METERS_PER_INCH,
+            12.0,
+        );
+        assert!((sg - 1.40).abs() < 0.01, "{sg}");
+        assert!(sg > UNSTABLE_STABILITY);
+    }
+
+    #[test]
+    fn correction_favours_speed_and_thin_air() {
+        let standard = air_density(0.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+        let sg = corrected_stability(1.5, MILLER_VELOCITY, standard);
+        assert!((sg - 1.5).abs() < 1e-12, "{sg}");
+
+        assert!(corrected_stability(1.5, 700.0, standard) < sg);
+        let high = air_density(2000.0, STANDARD_TEMPERATURE, STANDARD_PRESSURE, 0.0);
+        assert!(corrected_stability(1.5, MILLER_VELOCITY, high) > sg);
+    }
+}
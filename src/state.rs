@@ -0,0 +1,623 @@
+use crate::atmosphere::{STANDARD_PRESSURE, STANDARD_TEMPERATURE};
+use crate::ballistics::{Frame, Integrator, ShotParams};
+use crate::dispersion::Spread;
+use crate::drag::{BcBand, BcBands, DragModel, DragTable, MAX_BC_BANDS};
+use crate::error::BallisticError;
+use crate::history::History;
+use crate::profile::ShotProfile;
+use crate::spin::TwistDirection;
+use crate::units::{AngleUnit, UnitSystem};
+use crate::validation::validate;
+use crate::wind::{WindProfile, WindSample, MAX_WIND_SAMPLES};
+
+/// Everything the calculator's form edits, kept together so loading,
+/// resetting and undoing replace it all at once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShotState {
+    /// The shot as entered, apart from its wind samples and BC bands.
+    pub shot: ShotParams,
+    /// Edited as lists so rows can be added before they're filled in.
+    pub wind_samples: Vec<WindSample>,
+    pub bc_bands: Vec<BcBand>,
+    /// The last drag table read, so it can be picked again after switching
+    /// to a standard model.
+    pub custom_drag: Option<DragTable>,
+    /// Zero distance in meters.
+    pub zero_distance: f64,
+    /// Sight height above the bore in meters.
+    pub sight_height: f64,
+    pub target: Target,
+    pub tables: TableLayout,
+    pub group: GroupSettings,
+    /// A BC to convert to the other standard model; zero means none entered.
+    pub converter_bc: f64,
+    pub converter_model: DragModel,
+    pub unit_system: UnitSystem,
+    /// Only changes what heights are shown from; the simulation is the same.
+    pub frame: Frame,
+    pub elevation_unit: AngleUnit,
+    /// How far one turret click moves the impact, in `click_unit`.
+    pub click_value: f64,
+    pub click_unit: AngleUnit,
+    /// A second load to compare against; comparison is off while there's
+    /// none.
+    pub load_b: Option<ShotProfile>,
+    /// Whether the flight is playing rather than paused.
+    pub running: bool,
+    /// Playback speed against real time.
+    pub time_scale: f64,
+    /// Shows only the range card, ready to print, in place of the calculator.
+    pub print_view: bool,
+    pub history: History<ShotProfile>,
+    /// Bumped whenever the inputs are replaced wholesale, so fields showing
+    /// the old numbers can be remounted.
+    pub revision: u32,
+}
+
+impl Default for ShotState {
+    fn default() -> Self {
+        let profile = ShotProfile::default();
+        ShotState {
+            shot: profile.shot,
+            wind_samples: Vec::new(),
+            bc_bands: Vec::new(),
+            custom_drag: None,
+            zero_distance: profile.zero_distance,
+            sight_height: profile.sight_height,
+            target: Target::default(),
+            tables: TableLayout::default(),
+            group: GroupSettings::default(),
+            converter_bc: 0.0,
+            converter_model: DragModel::G1,
+            unit_system: UnitSystem::default(),
+            frame: Frame::default(),
+            elevation_unit: AngleUnit::default(),
+            click_value: 0.25,
+            click_unit: AngleUnit::Moa,
+            load_b: None,
+            running: true,
+            time_scale: 1.0,
+            print_view: false,
+            history: History::default(),
+            revision: 0,
+        }
+    }
+}
+
+/// What the shot is aimed at, in meters, m/s, joules and degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Target {
+    pub range: f64,
+    pub height: f64,
+    /// Positive uphill.
+    pub look_angle: f64,
+    /// How close to the target's center still counts as a hit.
+    pub hit_radius: f64,
+    /// Height of the target, for the danger space.
+    pub size: f64,
+    /// Diameter of the zone point-blank range keeps the shot inside.
+    pub vital_zone: f64,
+    /// Zero means no minimum has been set.
+    pub min_energy: f64,
+    pub speed: f64,
+    /// Direction the target moves in, 90° being straight across.
+    pub angle: f64,
+}
+
+impl Default for Target {
+    fn default() -> Self {
+        Target {
+            range: 100.0,
+            height: 0.0,
+            look_angle: 0.0,
+            hit_radius: 0.1,
+            size: 1.8,
+            vital_zone: 0.2,
+            min_energy: 0.0,
+            speed: 0.0,
+            angle: 90.0,
+        }
+    }
+}
+
+/// Where the range table's rows fall and what extras it carries, in meters
+/// and m/s.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TableLayout {
+    pub step: f64,
+    pub max: f64,
+    /// Crosswind the wind holds are given for; zero means none.
+    pub reference_wind: f64,
+    /// Estimated range to bracket; zero means none.
+    pub bracket_center: f64,
+    pub bracket_step: f64,
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        TableLayout {
+            step: 100.0,
+            max: 1000.0,
+            reference_wind: 0.0,
+            bracket_center: 0.0,
+            bracket_step: 50.0,
+        }
+    }
+}
+
+/// How a simulated group is fired.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GroupSettings {
+    pub shots: usize,
+    pub spread: Spread,
+    /// Picks the random draws, so the same seed gives the same group.
+    pub seed: u64,
+}
+
+impl Default for GroupSettings {
+    fn default() -> Self {
+        GroupSettings {
+            shots: 10,
+            spread: Spread {
+                velocity_sd: 3.0,
+                wind_min: 0.0,
+                wind_max: 0.0,
+            },
+            seed: 1,
+        }
+    }
+}
+
+// Actions are short-lived, so carrying profiles and drag tables inline is
+// cheaper than boxing them.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShotAction {
+    SetWind(f64),
+    SetWindDirection(f64),
+    AddWindSample,
+    RemoveWindSample,
+    SetWindSample(usize, WindSample),
+    SetElevation(f64),
+    SetCaliber(f64),
+    SetBallisticCoefficient(f64),
+    AddBcBand,
+    RemoveBcBand,
+    SetBcBand(usize, BcBand),
+    SetMuzzleVelocity(f64),
+    SetDragModel(DragModel),
+    /// Switches to a newly read drag table.
+    UseDragTable(DragTable),
+    SetLatitude(f64),
+    SetAzimuth(f64),
+    SetTwistRate(f64),
+    SetTwistDirection(TwistDirection),
+    SetBulletLength(f64),
+    SetMass(f64),
+    SetAltitude(f64),
+    SetTemperature(f64),
+    SetPressure(f64),
+    SetHumidity(f64),
+    /// The ICAO standard day at sea level, for comparing against published
+    /// tables.
+    SetStandardConditions,
+    SetVelocityTempSensitivity(f64),
+    SetReferenceTemperature(f64),
+    SetIntegrator(Integrator),
+    SetDragEnabled(bool),
+    SetMagnusEnabled(bool),
+    SetEotvosEnabled(bool),
+    SetTimeStep(f64),
+    SetMaxFlightTime(f64),
+    SetMaxRange(f64),
+    SetZeroDistance(f64),
+    SetSightHeight(f64),
+    SetTarget(Target),
+    SetTableLayout(TableLayout),
+    SetGroup(GroupSettings),
+    SetConverterBc(f64),
+    SetConverterModel(DragModel),
+    SetUnitSystem(UnitSystem),
+    SetFrame(Frame),
+    SetElevationUnit(AngleUnit),
+    SetClickValue(f64),
+    SetClickUnit(AngleUnit),
+    SetLoadB(Option<ShotProfile>),
+    SetRunning(bool),
+    TogglePause,
+    SetTimeScale(f64),
+    SetPrintView(bool),
+    /// Records the current inputs in the history, unless they're invalid.
+    Fire,
+    /// Back to the defaults, as an undoable step. Undo only brings the shot
+    /// back; the rest of the form stays reset.
+    Reset,
+    LoadProfile(ShotProfile),
+    Undo,
+    Redo,
+}
+
+impl ShotState {
+    /// The shot to simulate. Bands whose coefficient hasn't been filled in
+    /// yet don't count.
+    pub fn params(&self) -> ShotParams {
+        let bands: Vec<BcBand> = self
+            .bc_bands
+            .iter()
+            .copied()
+            .filter(|band| band.ballistic_coefficient > 0.0)
+            .collect();
+        ShotParams {
+            wind_profile: WindProfile::from_slice(&self.wind_samples),
+            bc_bands: BcBands::from_slice(&bands),
+            ..self.shot
+        }
+    }
+
+    /// The shot a submit fires, or everything wrong with it.
+    pub fn shot_to_fire(&self) -> Result<ShotParams, Vec<BallisticError>> {
+        let params = self.params();
+        validate(&params)?;
+        Ok(params)
+    }
+
+    pub fn profile(&self) -> ShotProfile {
+        ShotProfile::new(self.params(), self.zero_distance, self.sight_height)
+    }
+
+    pub fn apply(&mut self, action: ShotAction) {
+        let shot = &mut self.shot;
+        match action {
+            ShotAction::SetWind(value) => shot.wind = value,
+            ShotAction::SetWindDirection(value) => shot.wind_direction = value,
+            ShotAction::AddWindSample => {
+                if self.wind_samples.len() < MAX_WIND_SAMPLES {
+                    self.wind_samples.push(WindSample::default());
+                }
+            }
+            ShotAction::RemoveWindSample => {
+                self.wind_samples.pop();
+            }
+            ShotAction::SetWindSample(index, sample) => {
+                if let Some(slot) = self.wind_samples.get_mut(index) {
+                    *slot = sample;
+                }
+            }
+            ShotAction::SetElevation(value) => shot.elevation = value,
+            ShotAction::SetCaliber(value) => shot.caliber = value,
+            ShotAction::SetBallisticCoefficient(value) => shot.ballistic_coefficient = value,
+            ShotAction::AddBcBand => {
+                if self.bc_bands.len() < MAX_BC_BANDS {
+                    self.bc_bands.push(BcBand::default());
+                }
+            }
+            ShotAction::RemoveBcBand => {
+                self.bc_bands.pop();
+            }
+            ShotAction::SetBcBand(index, band) => {
+                if let Some(slot) = self.bc_bands.get_mut(index) {
+                    *slot = band;
+                }
+            }
+            ShotAction::SetMuzzleVelocity(value) => shot.muzzle_velocity = value,
+            ShotAction::SetDragModel(model) => shot.drag_model = model,
+            ShotAction::UseDragTable(table) => {
+                self.custom_drag = Some(table);
+                shot.drag_model = DragModel::Custom(table);
+            }
+            ShotAction::SetLatitude(value) => shot.latitude = value,
+            ShotAction::SetAzimuth(value) => shot.azimuth = value,
+            ShotAction::SetTwistRate(value) => shot.twist_rate = value,
+            ShotAction::SetTwistDirection(direction) => shot.twist_direction = direction,
+            ShotAction::SetBulletLength(value) => shot.bullet_length = value,
+            ShotAction::SetMass(value) => shot.mass = value,
+            ShotAction::SetAltitude(value) => shot.altitude = value,
+            ShotAction::SetTemperature(value) => shot.temperature = value,
+            ShotAction::SetPressure(value) => shot.pressure = value,
+            ShotAction::SetHumidity(value) => shot.humidity = value,
+            ShotAction::SetStandardConditions => {
+                shot.altitude = 0.0;
+                shot.temperature = STANDARD_TEMPERATURE;
+                shot.pressure = STANDARD_PRESSURE;
+                shot.humidity = 0.0;
+            }
+            ShotAction::SetVelocityTempSensitivity(value) => {
+                shot.velocity_temp_sensitivity = value;
+            }
+            ShotAction::SetReferenceTemperature(value) => shot.reference_temperature = value,
+            ShotAction::SetIntegrator(integrator) => shot.integrator = integrator,
+            ShotAction::SetDragEnabled(enabled) => shot.drag_enabled = enabled,
+            ShotAction::SetMagnusEnabled(enabled) => shot.magnus_enabled = enabled,
+            ShotAction::SetEotvosEnabled(enabled) => shot.eotvos_enabled = enabled,
+            ShotAction::SetTimeStep(value) => shot.time_step = value,
+            ShotAction::SetMaxFlightTime(value) => shot.max_flight_time = value,
+            ShotAction::SetMaxRange(value) => shot.max_range = value,
+            ShotAction::SetZeroDistance(value) => self.zero_distance = value,
+            ShotAction::SetSightHeight(value) => self.sight_height = value,
+            ShotAction::SetTarget(target) => self.target = target,
+            ShotAction::SetTableLayout(tables) => self.tables = tables,
+            ShotAction::SetGroup(group) => self.group = group,
+            ShotAction::SetConverterBc(value) => self.converter_bc = value,
+            ShotAction::SetConverterModel(model) => self.converter_model = model,
+            ShotAction::SetUnitSystem(units) => self.unit_system = units,
+            ShotAction::SetFrame(frame) => self.frame = frame,
+            ShotAction::SetElevationUnit(unit) => self.elevation_unit = unit,
+            ShotAction::SetClickValue(value) => self.click_value = value,
+            ShotAction::SetClickUnit(unit) => self.click_unit = unit,
+            ShotAction::SetLoadB(load) => self.load_b = load,
+            ShotAction::SetRunning(running) => self.running = running,
+            ShotAction::TogglePause => self.running = !self.running,
+            ShotAction::SetTimeScale(value) => self.time_scale = value,
+            ShotAction::SetPrintView(shown) => self.print_view = shown,
+            ShotAction::Fire => {
+                if self.shot_to_fire().is_ok() {
+                    self.history.record(self.profile());
+                }
+            }
+            ShotAction::Reset => {
+                self.history.record(self.profile());
+                let history = std::mem::take(&mut self.history);
+                let revision = self.revision;
+                *self = ShotState {
+                    history,
+                    revision,
+                    ..ShotState::default()
+                };
+                self.load(ShotProfile::default());
+                self.history.record(self.profile());
+            }
+            ShotAction::LoadProfile(profile) => {
+                self.history.record(self.profile());
+                self.load(profile);
+                self.history.record(self.profile());
+            }
+            // Unsubmitted edits are recorded first so redo can bring them back.
+            ShotAction::Undo => {
+                self.history.record(self.profile());
+                if let Some(previous) = self.history.undo() {
+                    self.load(previous);
+                }
+            }
+            ShotAction::Redo => {
+                if let Some(next) = self.history.redo() {
+                    self.load(next);
+                }
+            }
+        }
+    }
+
+    fn load(&mut self, profile: ShotProfile) {
+        let shot = profile.shot;
+        self.shot = shot;
+        self.wind_samples = shot.wind_profile.iter().collect();
+        self.bc_bands = shot.bc_bands.iter().collect();
+        if let DragModel::Custom(table) = shot.drag_model {
+            self.custom_drag = Some(table);
+        }
+        self.zero_distance = profile.zero_distance;
+        self.sight_height = profile.sight_height;
+        self.revision += 1;
+    }
+
+    /// Whether [`ShotAction::Undo`] would change anything.
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+            || self
+                .history
+                .current()
+                .is_some_and(|current| *current != self.profile())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl yew::functional::Reducible for ShotState {
+    type Action = ShotAction;
+
+    fn reduce(self: std::rc::Rc<Self>, action: ShotAction) -> std::rc::Rc<Self> {
+        let mut state = (*self).clone();
+        state.apply(action);
+        state.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ballistics::simulate_to_impact;
+
+    fn applied(actions: impl IntoIterator<Item = ShotAction>) -> ShotState {
+        let mut state = ShotState::default();
+        for action in actions {
+            state.apply(action);
+        }
+        state
+    }
+
+    #[test]
+    fn setters_change_only_their_field() {
+        let state = applied([ShotAction::SetWind(4.0), ShotAction::SetElevation(0.2)]);
+        assert_eq!(
+            state.params(),
+            ShotParams {
+                wind: 4.0,
+                elevation: 0.2,
+                ..ShotParams::default()
+            }
+        );
+        assert_eq!(state.revision, 0);
+    }
+
+    #[test]
+    fn standard_conditions_set_every_atmosphere_field() {
+        let state = applied([
+            ShotAction::SetAltitude(1500.0),
+            ShotAction::SetTemperature(32.0),
+            ShotAction::SetPressure(990.0),
+            ShotAction::SetHumidity(70.0),
+            ShotAction::SetWind(4.0),
+            ShotAction::SetStandardConditions,
+        ]);
+        assert_eq!(
+            state.params(),
+            ShotParams {
+                wind: 4.0,
+                ..ShotParams::default()
+            }
+        );
+    }
+
+    #[test]
+    fn unfilled_bc_bands_are_left_out_of_the_shot() {
+        let band = BcBand {
+            velocity: 600.0,
+            ballistic_coefficient: 0.45,
+        };
+        let state = applied([
+            ShotAction::AddBcBand,
+            ShotAction::AddBcBand,
+            ShotAction::SetBcBand(0, band),
+        ]);
+        assert_eq!(state.bc_bands.len(), 2);
+        assert_eq!(state.params().bc_bands.iter().collect::<Vec<_>>(), [band]);
+
+        let too_many = applied((0..MAX_WIND_SAMPLES + 2).map(|_| ShotAction::AddWindSample));
+        assert_eq!(too_many.wind_samples.len(), MAX_WIND_SAMPLES);
+    }
+
+    #[test]
+    fn loading_a_profile_replaces_everything_and_remounts() {
+        let shot = ShotParams {
+            muzzle_velocity: 790.0,
+            wind_profile: WindProfile::from_slice(&[WindSample {
+                height: 10.0,
+                speed: 3.0,
+                direction: 90.0,
+            }]),
+            ..ShotParams::default()
+        };
+        let profile = ShotProfile::new(shot, 200.0, 0.04);
+        let state = applied([ShotAction::SetWind(6.0), ShotAction::LoadProfile(profile)]);
+        assert_eq!(state.profile(), profile);
+        assert_eq!(state.wind_samples.len(), 1);
+        assert_eq!(state.revision, 1);
+    }
+
+    #[test]
+    fn reset_forgets_the_inputs_but_can_be_undone() {
+        let table = DragTable::new(&[(0.0, 0.2), (3.0, 0.3)]).unwrap();
+        let mut state = applied([
+            ShotAction::SetMass(0.012),
+            ShotAction::UseDragTable(table),
+            ShotAction::Reset,
+        ]);
+        assert_eq!(state.profile(), ShotProfile::default());
+        assert_eq!(state.custom_drag, None);
+
+        state.apply(ShotAction::Undo);
+        assert_eq!(state.shot.mass, 0.012);
+        assert_eq!(state.shot.drag_model, DragModel::Custom(table));
+        assert_eq!(state.custom_drag, Some(table));
+    }
+
+    #[test]
+    fn reset_puts_the_rest_of_the_form_back_too() {
+        let target = Target {
+            range: 600.0,
+            hit_radius: 0.25,
+            ..Target::default()
+        };
+        let tables = TableLayout {
+            step: 25.0,
+            ..TableLayout::default()
+        };
+        let mut state = applied([
+            ShotAction::SetTarget(target),
+            ShotAction::SetTableLayout(tables),
+            ShotAction::SetUnitSystem(UnitSystem::Imperial),
+            ShotAction::SetLoadB(Some(ShotProfile::default())),
+            ShotAction::TogglePause,
+            ShotAction::SetTimeScale(4.0),
+            ShotAction::SetWind(3.0),
+            ShotAction::Fire,
+        ]);
+        assert_eq!(state.target, target);
+        assert_eq!(state.tables.step, 25.0);
+        assert!(!state.running);
+
+        state.apply(ShotAction::Reset);
+        let fresh = ShotState::default();
+        assert_eq!(state.target, fresh.target);
+        assert_eq!(state.tables, fresh.tables);
+        assert_eq!(state.unit_system, fresh.unit_system);
+        assert_eq!(state.load_b, None);
+        assert!(state.running);
+        assert_eq!(state.time_scale, 1.0);
+        assert_eq!(state.revision, 1);
+
+        // Undo brings the shot back but not the rest of the form.
+        state.apply(ShotAction::Undo);
+        assert_eq!(state.shot.wind, 3.0);
+        assert_eq!(state.target, fresh.target);
+    }
+
+    #[test]
+    fn form_settings_stay_out_of_the_shot_and_its_history() {
+        let group = GroupSettings {
+            shots: 30,
+            ..GroupSettings::default()
+        };
+        let mut state = applied([
+            ShotAction::SetGroup(group),
+            ShotAction::SetClickValue(0.1),
+            ShotAction::SetClickUnit(AngleUnit::Mil),
+            ShotAction::SetFrame(Frame::Bore),
+            ShotAction::SetPrintView(true),
+        ]);
+        assert_eq!(state.profile(), ShotProfile::default());
+        assert!(state.history.is_empty());
+        assert!(!state.can_undo());
+        assert_eq!(state.group.shots, 30);
+        assert_eq!((state.click_value, state.click_unit), (0.1, AngleUnit::Mil));
+        assert!(state.print_view);
+
+        state.apply(ShotAction::LoadProfile(ShotProfile::default()));
+        assert_eq!(state.frame, Frame::Bore);
+        assert_eq!(state.group, group);
+    }
+
+    #[test]
+    fn invalid_shots_are_not_fired() {
+        let state = applied([ShotAction::SetCaliber(0.0), ShotAction::Fire]);
+        assert_eq!(
+            state.shot_to_fire(),
+            Err(vec![BallisticError::InvalidCaliber])
+        );
+        assert!(state
+            .shot_to_fire()
+            .map(|params| simulate_to_impact(&params))
+            .is_err());
+        assert!(state.history.is_empty());
+
+        let fixed = applied([ShotAction::SetCaliber(0.0), ShotAction::SetCaliber(0.00762)]);
+        let params = fixed.shot_to_fire().unwrap();
+        assert!(simulate_to_impact(&params).unwrap().len() > 1);
+    }
+
+    #[test]
+    fn undo_returns_to_the_last_fired_shot_and_redo_to_the_edit() {
+        let mut state = applied([
+            ShotAction::SetWind(2.0),
+            ShotAction::Fire,
+            ShotAction::SetWind(5.0),
+        ]);
+        assert!(state.can_undo());
+        state.apply(ShotAction::Undo);
+        assert_eq!(state.shot.wind, 2.0);
+        state.apply(ShotAction::Redo);
+        assert_eq!(state.shot.wind, 5.0);
+
+        // Nothing fired yet means nothing to go back to.
+        let fresh = applied([ShotAction::SetWind(1.0)]);
+        assert!(!fresh.can_undo());
+    }
+}
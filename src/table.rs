@@ -0,0 +1,614 @@
+use serde::Serialize;
+
+use crate::ballistics::{
+    height_above_sight_line, point_at_range, simulate_to_range, Frame, ShotParams, TrajectoryPoint,
+};
+use crate::scope::{clicks, correction, Clicks, Correction};
+use crate::spin::{spin_drift, TwistDirection};
+use crate::units::{AngleUnit, Quantity, UnitSystem};
+use crate::wind::WindProfile;
+
+/// Noted beside every drop column shown or exported, which give the drop as
+/// a height against the line of sight.
+pub const DROP_SIGN: &str = "− below sight line";
+
+/// One line of a downrange drop chart, in SI units.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct RangeRow {
+    pub range: f64,
+    /// Distance below the line of sight; negative while the bullet is above it.
+    pub drop: f64,
+    /// Lateral deflection, positive to the right.
+    pub drift: f64,
+    pub velocity: f64,
+    /// Kinetic energy in joules.
+    pub energy: f64,
+    pub time: f64,
+}
+
+impl RangeRow {
+    /// The drop as it's shown: height against the line of sight, negative
+    /// below it.
+    pub fn height(&self) -> f64 {
+        -self.drop
+    }
+}
+
+/// One line of a lateral deflection chart, broken down by cause, in meters
+/// with positive to the right.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct LateralRow {
+    pub range: f64,
+    pub spin_drift: f64,
+    pub coriolis: f64,
+    pub crosswind: f64,
+    /// The rest of the actual deflection: the Magnus drift when it's on, and
+    /// the small step error in following each part on its own.
+    pub other: f64,
+    /// The sum of the parts, which is the actual deflection plus spin drift.
+    pub total: f64,
+}
+
+/// The same range in two loads' drop charts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct ComparisonRow {
+    pub a: RangeRow,
+    pub b: RangeRow,
+}
+
+impl ComparisonRow {
+    /// How far load B comes out from load A, field by field, at A's range.
+    pub fn difference(&self) -> RangeRow {
+        let (a, b) = (self.a, self.b);
+        RangeRow {
+            range: a.range,
+            drop: b.drop - a.drop,
+            drift: b.drift - a.drift,
+            velocity: b.velocity - a.velocity,
+            energy: b.energy - a.energy,
+            time: b.time - a.time,
+        }
+    }
+}
+
+/// How far a reference crosswind moves the shot at one range, to scale by
+/// the wind actually blowing.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WindHoldRow {
+    pub range: f64,
+    /// Drift in meters, measured downwind.
+    pub drift: f64,
+    /// Hold into the wind to cancel it.
+    pub hold: Correction,
+}
+
+/// How much later the shot gets to a range than it would in a vacuum.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LagRow {
+    pub range: f64,
+    /// Lag time in seconds.
+    pub lag: f64,
+}
+
+/// One line of a printed range card: what to dial or hold at each range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CardRow {
+    pub range: f64,
+    /// Come-up for the drop; positive is dialled up.
+    pub elevation: Correction,
+    /// Hold for the drift; positive is to the right.
+    pub windage: Correction,
+    /// The come-up in turret clicks, if the click value is usable.
+    pub elevation_clicks: Option<Clicks>,
+    pub windage_clicks: Option<Clicks>,
+}
+
+/// Interpolates `points` at every `step` meters out to `max`, stopping early
+/// if the trajectory ends first.
+fn rows_at(
+    points: &[TrajectoryPoint],
+    step: f64,
+    max: f64,
+) -> impl Iterator<Item = TrajectoryPoint> + '_ {
+    (1..)
+        .map(move |i| i as f64 * step)
+        .take_while(move |range| step > 0.0 && *range <= max)
+        .map_while(|range| point_at_range(points, range))
+}
+
+fn range_row(point: &TrajectoryPoint, mass: f64, sight_height: f64) -> RangeRow {
+    let speed = point.velocity.magnitude();
+    RangeRow {
+        range: point.position.x,
+        drop: -height_above_sight_line(point, sight_height),
+        drift: point.position.z,
+        velocity: speed,
+        energy: 0.5 * mass * speed.powi(2),
+        time: point.time,
+    }
+}
+
+/// A drop chart row every `step` meters out to `max`.
+pub fn range_table(
+    points: &[TrajectoryPoint],
+    step: f64,
+    max: f64,
+    mass: f64,
+    sight_height: f64,
+) -> Vec<RangeRow> {
+    rows_at(points, step, max)
+        .map(|point| range_row(&point, mass, sight_height))
+        .collect()
+}
+
+/// Rows at an estimated range `center` and one and two `step`s either side
+/// of it, to bracket a target whose distance isn't known exactly. Ranges
+/// before the muzzle or past the end of `points` are left out.
+pub fn bracket(
+    points: &[TrajectoryPoint],
+    center: f64,
+    step: f64,
+    mass: f64,
+    sight_height: f64,
+) -> Vec<RangeRow> {
+    (-2..=2)
+        .map(|offset| center + offset as f64 * step)
+        .filter(|range| *range > 0.0)
+        .filter_map(|range| point_at_range(points, range))
+        .map(|point| range_row(&point, mass, sight_height))
+        .collect()
+}
+
+/// `rows` from [`range_table`] with each drop measured from `frame`'s line
+/// instead of the line of sight, for a bore at `elevation` degrees.
+pub fn in_frame(
+    rows: &[RangeRow],
+    frame: Frame,
+    elevation: f64,
+    sight_height: f64,
+) -> Vec<RangeRow> {
+    rows.iter()
+        .map(|row| RangeRow {
+            drop: row.drop + frame.reference_height(row.range, elevation, sight_height)
+                - sight_height,
+            ..*row
+        })
+        .collect()
+}
+
+/// Like [`range_table`], but splitting the sideways deflection into spin
+/// drift for a bullet of the given `stability`, Coriolis, and the wind, each
+/// followed separately through the flight, and whatever else moved it.
+pub fn lateral_table(
+    points: &[TrajectoryPoint],
+    step: f64,
+    max: f64,
+    stability: f64,
+    twist_direction: TwistDirection,
+) -> Vec<LateralRow> {
+    rows_at(points, step, max)
+        .map(|point| {
+            let spin_drift = spin_drift(stability, point.time, twist_direction);
+            let (coriolis, crosswind) = (point.coriolis_drift, point.wind_drift);
+            let other = point.position.z - coriolis - crosswind;
+            LateralRow {
+                range: point.position.x,
+                spin_drift,
+                coriolis,
+                crosswind,
+                other,
+                total: spin_drift + coriolis + crosswind + other,
+            }
+        })
+        .collect()
+}
+
+/// The drift from a full crosswind of `speed` m/s every `step` meters out to
+/// `max`, with any other wind in `params` taken away so only the reference
+/// wind counts.
+pub fn wind_holds(params: &ShotParams, speed: f64, step: f64, max: f64) -> Vec<WindHoldRow> {
+    let calm = ShotParams {
+        wind: 0.0,
+        wind_profile: WindProfile::default(),
+        ..*params
+    };
+    let windy = ShotParams {
+        wind: speed,
+        wind_direction: 90.0,
+        ..calm
+    };
+    let calm = simulate_to_range(&calm, max);
+    let windy = simulate_to_range(&windy, max);
+    rows_at(&windy, step, max)
+        .zip(rows_at(&calm, step, max))
+        .map(|(windy, calm)| {
+            // Wind from the right pushes the shot left.
+            let drift = calm.position.z - windy.position.z;
+            WindHoldRow {
+                range: windy.position.x,
+                drift,
+                hold: correction(drift, windy.position.x),
+            }
+        })
+        .collect()
+}
+
+/// Lag time every `step` meters out to `max`: time of flight less what the
+/// same launch would take with drag, wind and Coriolis switched off. A
+/// single number for how hard the air is holding the shot back.
+pub fn lag_times(params: &ShotParams, step: f64, max: f64) -> Vec<LagRow> {
+    let vacuum = ShotParams {
+        drag_enabled: false,
+        ..*params
+    };
+    let actual = simulate_to_range(params, max);
+    let vacuum = simulate_to_range(&vacuum, max);
+    rows_at(&actual, step, max)
+        .zip(rows_at(&vacuum, step, max))
+        .map(|(actual, vacuum)| LagRow {
+            range: actual.position.x,
+            lag: actual.time - vacuum.time,
+        })
+        .collect()
+}
+
+/// The corrections for each row of a drop chart, with clicks on a turret
+/// moving `click_value` of `click_unit` per click.
+pub fn range_card(rows: &[RangeRow], click_value: f64, click_unit: AngleUnit) -> Vec<CardRow> {
+    rows.iter()
+        .map(|row| {
+            let elevation = correction(row.drop, row.range);
+            let windage = correction(row.drift, row.range);
+            let dial = |hold: Correction| clicks(hold.mil, AngleUnit::Mil, click_value, click_unit);
+            CardRow {
+                range: row.range,
+                elevation,
+                windage,
+                elevation_clicks: dial(elevation),
+                windage_clicks: dial(windage),
+            }
+        })
+        .collect()
+}
+
+/// Pairs up two drop charts taken at the same step, as far as both go.
+pub fn compare_tables(a: &[RangeRow], b: &[RangeRow]) -> Vec<ComparisonRow> {
+    a.iter()
+        .zip(b)
+        .map(|(&a, &b)| ComparisonRow { a, b })
+        .collect()
+}
+
+/// Serializes `rows` as CSV in the given unit system, with a header naming
+/// each column's units.
+pub fn to_csv(rows: &[RangeRow], units: UnitSystem) -> String {
+    delimited(rows, units, ",")
+}
+
+/// The same as [`to_csv`] but tab-separated, which spreadsheets split into
+/// columns when it's pasted in.
+pub fn to_tsv(rows: &[RangeRow], units: UnitSystem) -> String {
+    delimited(rows, units, "\t")
+}
+
+fn delimited(rows: &[RangeRow], units: UnitSystem, separator: &str) -> String {
+    let header = [
+        format!("range ({})", units.unit(Quantity::Distance)),
+        format!("drop ({}; {})", units.unit(Quantity::Length), DROP_SIGN),
+        format!("drift ({})", units.unit(Quantity::Length)),
+        format!("velocity ({})", units.unit(Quantity::Velocity)),
+        "energy (J)".to_string(),
+        "time (s)".to_string(),
+    ];
+    let mut text = header.join(separator) + "\n";
+    for row in rows {
+        let fields = [
+            format!("{:.1}", units.from_si(Quantity::Distance, row.range)),
+            format!("{:.2}", units.from_si(Quantity::Length, row.height())),
+            format!("{:.2}", units.from_si(Quantity::Length, row.drift)),
+            format!("{:.1}", units.from_si(Quantity::Velocity, row.velocity)),
+            format!("{:.1}", row.energy),
+            format!("{:.4}", row.time),
+        ];
+        text.push_str(&fields.join(separator));
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ballistics::{simulate, simulate_to_range, ShotParams, GRAVITY};
+    use crate::profile::ShotProfile;
+
+    #[test]
+    fn hundred_meter_row_matches_hand_computation() {
+        // Negligible drag at 100 m/s: one second to 100 m and 0.5 * g * t² of drop.
+        let params = ShotParams {
+            caliber: 1.0,
+            ballistic_coefficient: 1e9,
+            muzzle_velocity: 100.0,
+            ..ShotParams::default()
+        };
+        let points = simulate_to_range(&params, 300.0);
+        let rows = range_table(&points, 100.0, 300.0, 0.01, 0.0);
+
+        assert_eq!(rows.len(), 3);
+        let row = rows[0];
+        assert!((row.range - 100.0).abs() < 1e-9);
+        assert!((row.time - 1.0).abs() < 0.01, "{row:?}");
+        assert!((row.drop - 0.5 * GRAVITY).abs() < 0.1, "{row:?}");
+        assert!((row.energy - 0.5 * 0.01 * row.velocity.powi(2)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn table_stops_where_the_trajectory_does() {
+        let params = ShotParams::default();
+        let points = simulate_to_range(&params, 250.0);
+        let rows = range_table(&points, 100.0, 1000.0, params.mass, 0.0);
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn bore_frame_shifts_each_drop_by_the_sight_geometry() {
+        let params = ShotParams {
+            elevation: 0.08,
+            ..ShotParams::default()
+        };
+        let points = simulate_to_range(&params, 500.0);
+        let rows = range_table(&points, 100.0, 500.0, params.mass, 0.04);
+        assert_eq!(
+            in_frame(&rows, Frame::LineOfSight, params.elevation, 0.04),
+            rows
+        );
+
+        let bore = in_frame(&rows, Frame::Bore, params.elevation, 0.04);
+        for (los, bore) in rows.iter().zip(&bore) {
+            let shift = los.range * params.elevation.to_radians().tan() - 0.04;
+            assert!((bore.drop - los.drop - shift).abs() < 1e-12, "{bore:?}");
+            assert_eq!((bore.range, bore.velocity), (los.range, los.velocity));
+        }
+    }
+
+    #[test]
+    fn lateral_components_add_up_to_the_total() {
+        let params = ShotParams {
+            wind: 4.0,
+            wind_direction: 270.0,
+            latitude: 45.0,
+            azimuth: 90.0,
+            ..ShotParams::default()
+        };
+        let points = simulate_to_range(&params, 800.0);
+        let rows = lateral_table(&points, 200.0, 800.0, 1.8, TwistDirection::Right);
+
+        let adds_up = |row: &LateralRow, points: &[TrajectoryPoint]| {
+            let sum = row.spin_drift + row.coriolis + row.crosswind + row.other;
+            assert!((row.total - sum).abs() < 1e-9, "{row:?}");
+            let deflection = row.total - row.spin_drift;
+            let point = point_at_range(points, row.range).unwrap();
+            assert!((deflection - point.position.z).abs() < 1e-9, "{row:?}");
+        };
+
+        assert_eq!(rows.len(), 4);
+        for row in &rows {
+            adds_up(row, &points);
+            assert!(row.coriolis != 0.0 && row.crosswind != 0.0, "{row:?}");
+            // Only step error is left over when nothing else pushes sideways.
+            assert!(row.other.abs() < 5e-3 * row.total.abs(), "{row:?}");
+        }
+        // The wind accounts for far more than Coriolis at these ranges.
+        let last = rows[3];
+        assert!(
+            last.crosswind.abs() > 10.0 * last.coriolis.abs(),
+            "{last:?}"
+        );
+
+        // The Magnus drift lands in the rest.
+        let magnus = ShotParams {
+            magnus_enabled: true,
+            ..params
+        };
+        let points = simulate_to_range(&magnus, 800.0);
+        let with_magnus = lateral_table(&points, 200.0, 800.0, 1.8, TwistDirection::Right);
+        for (row, plain) in with_magnus.iter().zip(&rows) {
+            adds_up(row, &points);
+            assert!(row.other != plain.other, "{row:?}");
+        }
+
+        // Without wind, Coriolis is all there is besides spin drift.
+        let calm = ShotParams {
+            wind: 0.0,
+            ..params
+        };
+        let points = simulate_to_range(&calm, 800.0);
+        let last = lateral_table(&points, 800.0, 800.0, 1.8, TwistDirection::Right)[0];
+        assert!(
+            last.crosswind.abs() < 0.01 * last.coriolis.abs(),
+            "{last:?}"
+        );
+    }
+
+    #[test]
+    fn identical_loads_overlay_exactly() {
+        let profile = ShotProfile::default();
+        let (a, b) = (profile, profile);
+        let table = |profile: &ShotProfile| {
+            let points = simulate_to_range(&profile.shot, 600.0);
+            range_table(
+                &points,
+                100.0,
+                600.0,
+                profile.shot.mass,
+                profile.sight_height,
+            )
+        };
+        assert_eq!(simulate(&a.shot).unwrap(), simulate(&b.shot).unwrap());
+
+        let rows = compare_tables(&table(&a), &table(&b));
+        assert_eq!(rows.len(), 6);
+        for row in &rows {
+            assert_eq!(row.a, row.b);
+            let difference = row.difference();
+            assert_eq!(difference.range, row.a.range);
+            assert_eq!(
+                (difference.drop, difference.velocity, difference.time),
+                (0.0, 0.0, 0.0)
+            );
+        }
+    }
+
+    #[test]
+    fn comparison_stops_at_the_shorter_table() {
+        let slow = ShotParams {
+            muzzle_velocity: 400.0,
+            ..ShotParams::default()
+        };
+        let fast = ShotParams::default();
+        let a = range_table(
+            &simulate_to_range(&slow, 300.0),
+            100.0,
+            300.0,
+            slow.mass,
+            0.0,
+        );
+        let b = range_table(
+            &simulate_to_range(&fast, 200.0),
+            100.0,
+            300.0,
+            fast.mass,
+            0.0,
+        );
+        let rows = compare_tables(&a, &b);
+        assert_eq!(rows.len(), 2);
+        // The faster load drops less and keeps more speed.
+        assert!(rows[1].difference().drop < 0.0, "{:?}", rows[1]);
+        assert!(rows[1].difference().velocity > 0.0, "{:?}", rows[1]);
+    }
+
+    #[test]
+    fn wind_drift_scales_with_the_reference_wind() {
+        let params = ShotParams {
+            wind: 3.0,
+            wind_direction: 45.0,
+            latitude: 45.0,
+            ..ShotParams::default()
+        };
+        let one = wind_holds(&params, 1.0, 200.0, 800.0);
+        let five = wind_holds(&params, 5.0, 200.0, 800.0);
+        assert_eq!(one.len(), 4);
+        for (one, five) in one.iter().zip(&five) {
+            assert!(one.drift > 0.0, "{one:?}");
+            let ratio = five.drift / one.drift;
+            assert!((ratio - 5.0).abs() < 0.05, "{ratio} at {}", one.range);
+            assert!((five.hold.mil / one.hold.mil - ratio).abs() < 1e-9);
+        }
+        assert!(wind_holds(&params, 0.0, 200.0, 800.0)
+            .iter()
+            .all(|row| row.drift == 0.0));
+    }
+
+    #[test]
+    fn lag_time_is_zero_in_a_vacuum_and_grows_with_drag() {
+        let vacuum = ShotParams {
+            drag_enabled: false,
+            ..ShotParams::default()
+        };
+        let rows = lag_times(&vacuum, 100.0, 500.0);
+        assert_eq!(rows.len(), 5);
+        assert!(rows.iter().all(|row| row.lag == 0.0), "{rows:?}");
+
+        let rows = lag_times(&ShotParams::default(), 100.0, 500.0);
+        assert_eq!(rows.len(), 5);
+        assert!(rows[0].lag > 0.0);
+        assert!(rows.windows(2).all(|pair| pair[1].lag > pair[0].lag));
+    }
+
+    #[test]
+    fn bracket_centres_on_the_table_row() {
+        let params = ShotParams::default();
+        let points = simulate_to_range(&params, 700.0);
+        let table = range_table(&points, 100.0, 700.0, params.mass, 0.04);
+        let rows = bracket(&points, 500.0, 50.0, params.mass, 0.04);
+
+        let ranges: Vec<f64> = rows.iter().map(|row| row.range).collect();
+        assert_eq!(ranges, [400.0, 450.0, 500.0, 550.0, 600.0]);
+        assert_eq!(rows[2], table[4]);
+        assert!(rows.windows(2).all(|pair| pair[1].drop > pair[0].drop));
+
+        // Nothing before the muzzle.
+        assert_eq!(bracket(&points, 60.0, 50.0, params.mass, 0.04).len(), 4);
+    }
+
+    #[test]
+    fn range_card_dials_each_row() {
+        let rows = [
+            RangeRow {
+                range: 100.0,
+                drop: -0.02,
+                ..RangeRow::default()
+            },
+            RangeRow {
+                range: 500.0,
+                drop: 1.5,
+                drift: -0.25,
+                ..RangeRow::default()
+            },
+        ];
+        let card = range_card(&rows, 0.1, AngleUnit::Mil);
+        assert_eq!(card.len(), 2);
+        assert_eq!(card[0].elevation_clicks.unwrap().count, -2);
+        let far = card[1];
+        assert_eq!(far.range, 500.0);
+        assert!((far.elevation.mil - 3.0).abs() < 1e-12, "{far:?}");
+        assert_eq!(far.elevation_clicks.unwrap().count, 30);
+        assert_eq!(far.windage_clicks.unwrap().count, -5);
+        assert!(range_card(&rows, 0.0, AngleUnit::Moa)[1]
+            .elevation_clicks
+            .is_none());
+    }
+
+    #[test]
+    fn csv_has_a_header_and_one_line_per_row() {
+        let rows = [RangeRow {
+            range: 100.0,
+            drop: 0.05,
+            drift: -0.001,
+            velocity: 780.0,
+            energy: 3311.6,
+            time: 0.1234,
+        }];
+        assert_eq!(
+            to_csv(&rows, UnitSystem::Metric),
+            "range (m),drop (mm; − below sight line),drift (mm),velocity (m/s),energy (J),time (s)\n\
+             100.0,-50.00,-1.00,780.0,3311.6,0.1234\n"
+        );
+
+        let imperial = to_csv(&rows, UnitSystem::Imperial);
+        assert!(imperial.starts_with("range (yd),drop (in; − below sight line),drift (in)"));
+        assert_eq!(imperial.lines().count(), 2);
+    }
+
+    #[test]
+    fn tsv_matches_csv_with_tabs() {
+        let rows = [RangeRow {
+            range: 100.0,
+            drop: 0.05,
+            drift: -0.001,
+            velocity: 780.0,
+            energy: 3311.6,
+            time: 0.1234,
+        }];
+        assert_eq!(
+            to_tsv(&rows, UnitSystem::Metric),
+            "range (m)\tdrop (mm; − below sight line)\tdrift (mm)\tvelocity (m/s)\tenergy (J)\ttime (s)\n\
+             100.0\t-50.00\t-1.00\t780.0\t3311.6\t0.1234\n"
+        );
+        let imperial = to_tsv(&rows, UnitSystem::Imperial);
+        assert_eq!(
+            imperial.replace('\t', ","),
+            to_csv(&rows, UnitSystem::Imperial)
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::ballistics::TIME_STEP;
+
+/// Physics step for live playback until a flight says otherwise, the same
+/// default `simulate` uses so the animation agrees with the table and chart.
+pub const PHYSICS_DT: f64 = TIME_STEP;
+/// Longest wall-clock gap counted in one tick, so a backgrounded tab doesn't
+/// come back and run thousands of steps at once.
+pub const MAX_FRAME_TIME: f64 = 0.25;
+/// Slack for accumulated rounding, so ten 1 ms frames make exactly one step.
+const EPSILON: f64 = 1e-9;
+
+/// Turns irregular frame times into a whole number of fixed physics steps,
+/// carrying the remainder over to the next frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Accumulator {
+    carried: f64,
+    dt: f64,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Accumulator::new(PHYSICS_DT)
+    }
+}
+
+impl Accumulator {
+    /// Counts out steps of `dt` seconds.
+    pub fn new(dt: f64) -> Self {
+        Accumulator { carried: 0.0, dt }
+    }
+
+    /// Switches to steps of `dt` seconds. Time carried towards a step of the
+    /// old length is dropped.
+    pub fn set_dt(&mut self, dt: f64) {
+        if dt != self.dt {
+            self.dt = dt;
+            self.carried = 0.0;
+        }
+    }
+
+    /// Adds `elapsed` seconds of simulated time and returns how many steps
+    /// are now due.
+    pub fn advance(&mut self, elapsed: f64) -> usize {
+        self.carried += elapsed.clamp(0.0, MAX_FRAME_TIME);
+        let mut steps = 0;
+        while self.carried + EPSILON >= self.dt {
+            self.carried -= self.dt;
+            steps += 1;
+        }
+        self.carried = self.carried.max(0.0);
+        steps
+    }
+
+    /// How far into the next step the carried time reaches, from 0 to 1, for
+    /// blending between the last two physics states.
+    pub fn alpha(&self) -> f64 {
+        (self.carried / self.dt).clamp(0.0, 1.0)
+    }
+
+    pub fn reset(&mut self) {
+        self.carried = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_count_depends_only_on_total_time() {
+        let run = |frames: &[f64]| {
+            let mut accumulator = Accumulator::default();
+            frames
+                .iter()
+                .map(|frame| accumulator.advance(*frame))
+                .sum::<usize>()
+        };
+        assert_eq!(run(&[0.1]), 10);
+        assert_eq!(run(&[0.01; 10]), 10);
+        assert_eq!(run(&[0.001; 100]), 10);
+        assert_eq!(run(&[0.016, 0.017, 0.016, 0.017, 0.017, 0.017]), 10);
+    }
+
+    #[test]
+    fn carries_the_remainder_as_alpha() {
+        let mut accumulator = Accumulator::default();
+        assert_eq!(accumulator.advance(0.025), 2);
+        assert!((accumulator.alpha() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn long_stalls_are_capped() {
+        let mut accumulator = Accumulator::default();
+        assert_eq!(accumulator.advance(10.0), 25);
+        assert_eq!(accumulator.advance(-1.0), 0);
+    }
+
+    #[test]
+    fn finer_steps_come_more_often() {
+        let mut accumulator = Accumulator::new(0.001);
+        assert_eq!(accumulator.advance(0.1), 100);
+        accumulator.advance(0.0005);
+        accumulator.set_dt(0.02);
+        assert_eq!(accumulator.alpha(), 0.0);
+        assert_eq!(accumulator.advance(0.1), 5);
+    }
+}
@@ -0,0 +1,245 @@
+use std::str::FromStr;
+
+use crate::scope::{MIL_PER_RADIAN, MOA_PER_RADIAN};
+
+const METERS_PER_INCH: f64 = 0.0254;
+pub const METERS_PER_FOOT: f64 = 0.3048;
+const METERS_PER_YARD: f64 = 0.9144;
+const KILOGRAMS_PER_GRAIN: f64 = 6.479891e-5;
+pub const JOULES_PER_FOOT_POUND: f64 = 1.355_817_948;
+
+/// Energy is quoted in foot-pounds as often as joules whichever units the
+/// rest of the page uses, so it converts on its own rather than through
+/// [`UnitSystem`].
+pub fn foot_pounds(joules: f64) -> f64 {
+    joules / JOULES_PER_FOOT_POUND
+}
+
+/// Drops get cross-referenced in both centimeters and inches, so like
+/// [`foot_pounds`] these convert regardless of the unit system.
+pub fn centimeters(meters: f64) -> f64 {
+    meters * 100.0
+}
+
+/// See [`centimeters`].
+pub fn inches(meters: f64) -> f64 {
+    meters / METERS_PER_INCH
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnitSystem {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+/// The kinds of values the UI converts between unit systems.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantity {
+    /// Small lengths such as caliber and bullet length: mm or inches.
+    Length,
+    /// Speeds: m/s or feet per second.
+    Velocity,
+    /// Ranges and heights: meters or yards.
+    Distance,
+    /// Bullet mass: grams or grains.
+    Mass,
+}
+
+impl UnitSystem {
+    pub fn toggle(self) -> Self {
+        match self {
+            UnitSystem::Metric => UnitSystem::Imperial,
+            UnitSystem::Imperial => UnitSystem::Metric,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "Metric",
+            UnitSystem::Imperial => "Imperial",
+        }
+    }
+
+    pub fn unit(self, quantity: Quantity) -> &'static str {
+        match (self, quantity) {
+            (UnitSystem::Metric, Quantity::Length) => "mm",
+            (UnitSystem::Metric, Quantity::Velocity) => "m/s",
+            (UnitSystem::Metric, Quantity::Distance) => "m",
+            (UnitSystem::Metric, Quantity::Mass) => "g",
+            (UnitSystem::Imperial, Quantity::Length) => "in",
+            (UnitSystem::Imperial, Quantity::Velocity) => "fps",
+            (UnitSystem::Imperial, Quantity::Distance) => "yd",
+            (UnitSystem::Imperial, Quantity::Mass) => "gr",
+        }
+    }
+
+    /// Size of one display unit in SI units.
+    fn scale(self, quantity: Quantity) -> f64 {
+        match (self, quantity) {
+            (UnitSystem::Metric, Quantity::Length) => 0.001,
+            (UnitSystem::Metric, Quantity::Velocity) => 1.0,
+            (UnitSystem::Metric, Quantity::Distance) => 1.0,
+            (UnitSystem::Metric, Quantity::Mass) => 0.001,
+            (UnitSystem::Imperial, Quantity::Length) => METERS_PER_INCH,
+            (UnitSystem::Imperial, Quantity::Velocity) => METERS_PER_FOOT,
+            (UnitSystem::Imperial, Quantity::Distance) => METERS_PER_YARD,
+            (UnitSystem::Imperial, Quantity::Mass) => KILOGRAMS_PER_GRAIN,
+        }
+    }
+
+    pub fn to_si(self, quantity: Quantity, value: f64) -> f64 {
+        value * self.scale(quantity)
+    }
+
+    pub fn from_si(self, quantity: Quantity, value: f64) -> f64 {
+        value / self.scale(quantity)
+    }
+}
+
+/// Units the bore elevation can be entered in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AngleUnit {
+    #[default]
+    Degrees,
+    /// Milliradians, matching the MIL corrections shown for holds.
+    Mil,
+    Moa,
+}
+
+impl AngleUnit {
+    pub const ALL: [AngleUnit; 3] = [AngleUnit::Degrees, AngleUnit::Mil, AngleUnit::Moa];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AngleUnit::Degrees => "°",
+            AngleUnit::Mil => "MIL",
+            AngleUnit::Moa => "MOA",
+        }
+    }
+
+    fn per_radian(self) -> f64 {
+        match self {
+            AngleUnit::Degrees => 180.0 / std::f64::consts::PI,
+            AngleUnit::Mil => MIL_PER_RADIAN,
+            AngleUnit::Moa => MOA_PER_RADIAN,
+        }
+    }
+
+    pub fn to_radians(self, value: f64) -> f64 {
+        value / self.per_radian()
+    }
+
+    pub fn from_radians(self, radians: f64) -> f64 {
+        radians * self.per_radian()
+    }
+
+    pub fn to_degrees(self, value: f64) -> f64 {
+        self.to_radians(value).to_degrees()
+    }
+
+    pub fn from_degrees(self, degrees: f64) -> f64 {
+        self.from_radians(degrees.to_radians())
+    }
+}
+
+impl FromStr for AngleUnit {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AngleUnit::ALL
+            .into_iter()
+            .find(|unit| unit.label() == s)
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_joules_to_foot_pounds() {
+        assert!((foot_pounds(JOULES_PER_FOOT_POUND) - 1.0).abs() < 1e-12);
+        assert!((foot_pounds(1000.0) - 737.562).abs() < 0.001);
+    }
+
+    #[test]
+    fn converts_drop_to_centimeters_and_inches() {
+        let drop = 0.4572;
+        assert!((centimeters(drop) - 45.72).abs() < 1e-9);
+        assert!((inches(drop) - 18.0).abs() < 1e-9);
+    }
+
+    const QUANTITIES: [Quantity; 4] = [
+        Quantity::Length,
+        Quantity::Velocity,
+        Quantity::Distance,
+        Quantity::Mass,
+    ];
+
+    #[test]
+    fn imperial_conversions() {
+        let imperial = UnitSystem::Imperial;
+        assert!((imperial.to_si(Quantity::Length, 0.308) - 0.0078232).abs() < 1e-9);
+        assert!((imperial.from_si(Quantity::Velocity, 850.0) - 2788.71).abs() < 0.01);
+        assert!((imperial.to_si(Quantity::Distance, 100.0) - 91.44).abs() < 1e-9);
+        assert!((imperial.to_si(Quantity::Mass, 168.0) - 0.010886).abs() < 1e-6);
+    }
+
+    #[test]
+    fn common_grain_weights_round_trip_through_kilograms() {
+        let imperial = UnitSystem::Imperial;
+        for (grains, grams) in [
+            (55.0, 3.564),
+            (77.0, 4.990),
+            (168.0, 10.886),
+            (230.0, 14.904),
+        ] {
+            let kilograms = imperial.to_si(Quantity::Mass, grains);
+            assert!((kilograms * 1000.0 - grams).abs() < 1e-3, "{grains} gr");
+            assert!((UnitSystem::Metric.from_si(Quantity::Mass, kilograms) - grams).abs() < 1e-3);
+            assert!((imperial.from_si(Quantity::Mass, kilograms) - grains).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn metric_caliber_is_millimeters() {
+        assert!((UnitSystem::Metric.from_si(Quantity::Length, 0.00762) - 7.62).abs() < 1e-9);
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        for units in [UnitSystem::Metric, UnitSystem::Imperial] {
+            for quantity in QUANTITIES {
+                for value in [0.001, 1.0, 308.0, 2750.0, 1e6] {
+                    let round_trip = units.to_si(quantity, units.from_si(quantity, value));
+                    assert!((round_trip - value).abs() <= value * 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn toggle_flips_between_systems() {
+        assert_eq!(UnitSystem::Metric.toggle(), UnitSystem::Imperial);
+        assert_eq!(UnitSystem::Metric.toggle().toggle(), UnitSystem::Metric);
+    }
+
+    #[test]
+    fn angles_convert_between_units() {
+        assert!((AngleUnit::Moa.from_degrees(1.0) - 60.0).abs() < 0.01);
+        assert!((AngleUnit::Mil.from_degrees(1.0) - 17.4533).abs() < 1e-4);
+        assert!((AngleUnit::Mil.to_degrees(1000.0) - 57.2958).abs() < 1e-4);
+    }
+
+    #[test]
+    fn angles_round_trip_through_radians() {
+        for unit in AngleUnit::ALL {
+            for value in [0.0, 0.25, 1.0, 45.0, 1000.0] {
+                let round_trip = unit.from_radians(unit.to_radians(value));
+                assert!((round_trip - value).abs() <= value * 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::ballistics::{ShotParams, MAX_TIME_STEP, MIN_TIME_STEP};
+use crate::error::BallisticError;
+
+/// False for NaN as well as for zero and negative values.
+fn is_positive(value: f64) -> bool {
+    value > 0.0
+}
+
+fn is_valid_bc(ballistic_coefficient: f64) -> bool {
+    is_positive(ballistic_coefficient) && ballistic_coefficient <= 1.0
+}
+
+/// Rejects parameters the simulation can't make sense of at all: NaN, a
+/// caliber or BC that isn't positive, or a launch angle past vertical. Looser
+/// than [`validate`], so tests and embedders can still try extreme shots.
+pub fn check(params: &ShotParams) -> Result<(), BallisticError> {
+    let finite_positive = |value: f64| is_positive(value) && value.is_finite();
+    if !finite_positive(params.caliber) {
+        return Err(BallisticError::InvalidCaliber);
+    }
+    if !finite_positive(params.ballistic_coefficient)
+        || !params
+            .bc_bands
+            .iter()
+            .all(|band| finite_positive(band.ballistic_coefficient))
+    {
+        return Err(BallisticError::InvalidBc);
+    }
+    if !(-90.0..=90.0).contains(&params.elevation) {
+        return Err(BallisticError::InvalidAngle);
+    }
+    if !(params.muzzle_velocity >= 0.0 && params.muzzle_velocity.is_finite()) {
+        return Err(BallisticError::InvalidVelocity);
+    }
+    if !finite_positive(params.time_step) {
+        return Err(BallisticError::InvalidTimeStep);
+    }
+    Ok(())
+}
+
+/// Checks that `params` describe a shot worth showing in the calculator,
+/// returning every problem found.
+pub fn validate(params: &ShotParams) -> Result<(), Vec<BallisticError>> {
+    let mut errors = Vec::new();
+
+    if !is_positive(params.caliber) {
+        errors.push(BallisticError::InvalidCaliber);
+    }
+    if !is_valid_bc(params.ballistic_coefficient)
+        || params
+            .bc_bands
+            .iter()
+            .any(|band| !is_valid_bc(band.ballistic_coefficient))
+    {
+        errors.push(BallisticError::InvalidBc);
+    }
+    if !(0.0..=90.0).contains(&params.elevation) {
+        errors.push(BallisticError::InvalidAngle);
+    }
+    if !is_positive(params.muzzle_velocity) {
+        errors.push(BallisticError::InvalidVelocity);
+    }
+    if !(MIN_TIME_STEP..=MAX_TIME_STEP).contains(&params.time_step) {
+        errors.push(BallisticError::InvalidTimeStep);
+    }
+    // The flight doesn't depend on it, but energy and stability do.
+    if !is_positive(params.mass) {
+        errors.push(BallisticError::InvalidMass);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drag::{BcBand, BcBands};
+
+    fn errors(params: ShotParams) -> Vec<BallisticError> {
+        validate(&params).unwrap_err()
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(validate(&ShotParams::default()), Ok(()));
+        assert_eq!(check(&ShotParams::default()), Ok(()));
+    }
+
+    #[test]
+    fn rejects_non_positive_caliber() {
+        for caliber in [0.0, -0.00762, f64::NAN] {
+            let params = ShotParams {
+                caliber,
+                ..ShotParams::default()
+            };
+            assert_eq!(errors(params), [BallisticError::InvalidCaliber]);
+            assert_eq!(check(&params), Err(BallisticError::InvalidCaliber));
+        }
+    }
+
+    #[test]
+    fn rejects_ballistic_coefficient_out_of_range() {
+        for ballistic_coefficient in [0.0, -0.4, 1.2] {
+            let errors = errors(ShotParams {
+                ballistic_coefficient,
+                ..ShotParams::default()
+            });
+            assert_eq!(errors, [BallisticError::InvalidBc]);
+        }
+        let banded = ShotParams {
+            bc_bands: BcBands::from_slice(&[BcBand {
+                velocity: 0.0,
+                ballistic_coefficient: 0.0,
+            }]),
+            ..ShotParams::default()
+        };
+        assert_eq!(errors(banded), [BallisticError::InvalidBc]);
+        assert_eq!(check(&banded), Err(BallisticError::InvalidBc));
+    }
+
+    #[test]
+    fn rejects_elevation_out_of_range() {
+        for elevation in [-1.0, 90.5, 400.0] {
+            let errors = errors(ShotParams {
+                elevation,
+                ..ShotParams::default()
+            });
+            assert_eq!(errors, [BallisticError::InvalidAngle]);
+        }
+        let nan = ShotParams {
+            elevation: f64::NAN,
+            ..ShotParams::default()
+        };
+        assert_eq!(check(&nan), Err(BallisticError::InvalidAngle));
+    }
+
+    #[test]
+    fn rejects_non_positive_muzzle_velocity() {
+        let params = ShotParams {
+            muzzle_velocity: 0.0,
+            ..ShotParams::default()
+        };
+        assert_eq!(errors(params), [BallisticError::InvalidVelocity]);
+        let nan = ShotParams {
+            muzzle_velocity: f64::NAN,
+            ..params
+        };
+        assert_eq!(check(&nan), Err(BallisticError::InvalidVelocity));
+    }
+
+    #[test]
+    fn check_allows_extreme_but_runnable_shots() {
+        let extreme = ShotParams {
+            ballistic_coefficient: 1e9,
+            elevation: -10.0,
+            muzzle_velocity: 0.0,
+            ..ShotParams::default()
+        };
+        assert_eq!(check(&extreme), Ok(()));
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let errors = errors(ShotParams {
+            caliber: 0.0,
+            ballistic_coefficient: 0.0,
+            elevation: 400.0,
+            muzzle_velocity: -1.0,
+            ..ShotParams::default()
+        });
+        assert_eq!(errors.len(), 4);
+    }
+
+    #[test]
+    fn rejects_non_positive_mass() {
+        let params = ShotParams {
+            mass: 0.0,
+            ..ShotParams::default()
+        };
+        assert_eq!(errors(params), [BallisticError::InvalidMass]);
+        assert_eq!(check(&params), Ok(()));
+    }
+
+    #[test]
+    fn rejects_time_step_out_of_bounds() {
+        for time_step in [0.0, 0.0001, 0.1] {
+            let params = ShotParams {
+                time_step,
+                ..ShotParams::default()
+            };
+            assert_eq!(errors(params), [BallisticError::InvalidTimeStep]);
+        }
+        let zero = ShotParams {
+            time_step: 0.0,
+            ..ShotParams::default()
+        };
+        assert_eq!(check(&zero), Err(BallisticError::InvalidTimeStep));
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ballistics::{wind_velocity, Vector3};
+
+pub const MAX_WIND_SAMPLES: usize = 4;
+
+/// Wind measured at `height` meters above the firing point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindSample {
+    pub height: f64,
+    /// Speed in m/s.
+    pub speed: f64,
+    /// Degrees clockwise from the firing line that the wind blows from.
+    pub direction: f64,
+}
+
+/// Wind samples by height, kept fixed-size so shot parameters stay `Copy`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindProfile([Option<WindSample>; MAX_WIND_SAMPLES]);
+
+impl WindProfile {
+    /// Keeps the first `MAX_WIND_SAMPLES` samples, sorted by height.
+    pub fn from_slice(samples: &[WindSample]) -> Self {
+        let mut sorted: Vec<WindSample> = samples.iter().copied().take(MAX_WIND_SAMPLES).collect();
+        sorted.sort_by(|a, b| a.height.total_cmp(&b.height));
+
+        let mut slots = [None; MAX_WIND_SAMPLES];
+        for (slot, sample) in slots.iter_mut().zip(sorted) {
+            *slot = Some(sample);
+        }
+        WindProfile(slots)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = WindSample> + '_ {
+        self.0.iter().flatten().copied()
+    }
+
+    /// Air velocity at `height`, interpolated between the samples either side
+    /// and held constant beyond the lowest and highest. `None` when empty.
+    pub fn at(&self, height: f64) -> Option<Vector3> {
+        let velocity = |sample: WindSample| wind_velocity(sample.speed, sample.direction);
+        let mut samples = self.iter().peekable();
+        let mut below = *samples.peek()?;
+        if height <= below.height {
+            return Some(velocity(below));
+        }
+
+        for above in samples {
+            if height <= above.height {
+                let t = (height - below.height) / (above.height - below.height);
+                let (a, b) = (velocity(below), velocity(above));
+                return Some(Vector3 {
+                    x: a.x + (b.x - a.x) * t,
+                    y: a.y + (b.y - a.y) * t,
+                    z: a.z + (b.z - a.z) * t,
+                });
+            }
+            below = above;
+        }
+
+        Some(velocity(below))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(height: f64, speed: f64) -> WindSample {
+        WindSample {
+            height,
+            speed,
+            direction: 90.0,
+        }
+    }
+
+    #[test]
+    fn interpolates_between_heights() {
+        let profile = WindProfile::from_slice(&[sample(100.0, 10.0), sample(0.0, 2.0)]);
+        assert!((profile.at(50.0).unwrap().z + 6.0).abs() < 1e-12);
+        assert!((profile.at(-5.0).unwrap().z + 2.0).abs() < 1e-12);
+        assert!((profile.at(500.0).unwrap().z + 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn a_single_sample_is_constant() {
+        let profile = WindProfile::from_slice(&[sample(30.0, 4.0)]);
+        assert_eq!(profile.at(0.0), profile.at(1000.0));
+        assert_eq!(WindProfile::default().at(0.0), None);
+    }
+}
@@ -0,0 +1,54 @@
+use gloo_worker::{HandlerId, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+
+use crate::ballistics::{simulate_to_impact, ShotParams, TrajectoryPoint};
+
+/// Where trunk serves the worker bundle built from `src/bin/worker.rs`.
+pub const WORKER_PATH: &str = "/worker.js";
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrajectoryRequest {
+    /// Echoed back so stale answers to older submits can be dropped.
+    pub id: u32,
+    pub params: ShotParams,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrajectoryResponse {
+    pub id: u32,
+    pub points: Vec<TrajectoryPoint>,
+    /// The step the points are spaced by, for playing them back.
+    pub time_step: f64,
+}
+
+impl TrajectoryRequest {
+    /// The same computation the worker runs, for falling back to the main
+    /// thread.
+    pub fn run(&self) -> TrajectoryResponse {
+        TrajectoryResponse {
+            id: self.id,
+            // The form won't submit a shot that fails validation.
+            points: simulate_to_impact(&self.params).unwrap_or_default(),
+            time_step: self.params.time_step,
+        }
+    }
+}
+
+/// Runs `simulate_to_impact` off the main thread.
+pub struct TrajectoryWorker;
+
+impl Worker for TrajectoryWorker {
+    type Message = ();
+    type Input = TrajectoryRequest;
+    type Output = TrajectoryResponse;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        TrajectoryWorker
+    }
+
+    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+    fn received(&mut self, scope: &WorkerScope<Self>, request: Self::Input, id: HandlerId) {
+        scope.respond(id, request.run());
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::process::Command;
+
+fn run(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_ballistic-calc"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn prints_a_range_table_for_a_profile() {
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/profile.json");
+    let output = run(&[fixture, "100", "500"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let csv = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert!(
+        lines[0].starts_with("range (m),drop (mm; − below sight line)"),
+        "{csv}"
+    );
+    assert_eq!(lines.len(), 6, "{csv}");
+    assert!(lines[5].starts_with("500"), "{csv}");
+    // Well past the zero the shot is below the sight line.
+    let drop: f64 = lines[5].split(',').nth(1).unwrap().parse().unwrap();
+    assert!(drop < 0.0, "{csv}");
+}
+
+#[test]
+fn reports_a_missing_profile() {
+    let output = run(&["does-not-exist.json"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("could not read"));
+}
+
+#[test]
+fn rejects_a_profile_that_cannot_be_simulated() {
+    for (fixture, message) in [
+        ("negative_bc.json", "Ballistic coefficient"),
+        ("massless.json", "Bullet mass"),
+    ] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), fixture);
+        let output = run(&[&path]);
+        assert!(!output.status.success(), "{fixture}");
+        assert!(output.stdout.is_empty(), "{fixture}");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(message), "{fixture}: {stderr}");
+    }
+}
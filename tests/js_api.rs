@@ -0,0 +1,14 @@
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn solves_elevation_from_a_plain_object() {
+    let params = js_sys::JSON::parse(r#"{"muzzle_velocity": 800.0}"#).unwrap();
+    let elevation = enigma::js::solve_elevation(params, 300.0, 0.0)
+        .map_err(JsValue::from)
+        .unwrap()
+        .unwrap();
+    assert!(elevation > 0.0 && elevation < 1.0);
+}
@@ -0,0 +1,77 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+//! Times a playback tick, which only moves a playhead along the precomputed
+//! flight, against integrating the flight again up to the same moment. It's
+//! a measurement rather than a check, so it's ignored by default; run it with
+//! `cargo test --release --test playback -- --ignored --nocapture`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use enigma::ballistics::{
+    lerp_points, simulate, simulate_to_impact, ShotParams, TrajectoryPoint, MIN_TIME_STEP,
+};
+use enigma::timestep::Accumulator;
+
+/// Wall-clock time between ticks at 60 frames a second.
+const FRAME: f64 = 1.0 / 60.0;
+/// Re-integrating grows with the playhead, so only every this many ticks are
+/// timed to keep the run short in a debug build.
+const SAMPLE_EVERY: usize = 20;
+
+#[test]
+#[ignore]
+fn playback_ticks_are_cheaper_than_reintegrating() {
+    // A long flight at the finest step: the worst case for either approach.
+    let params = ShotParams {
+        elevation: 15.0,
+        time_step: MIN_TIME_STEP,
+        ..ShotParams::default()
+    }
+    .uncapped();
+
+    let start = Instant::now();
+    let flight = simulate_to_impact(&params).unwrap();
+    let precompute = start.elapsed();
+    let flight_time = flight.last().unwrap().time;
+    let ticks = (flight_time / FRAME).ceil() as usize;
+
+    let mut accumulator = Accumulator::new(params.time_step);
+    let mut playhead = 0;
+    let start = Instant::now();
+    for _ in 0..ticks {
+        playhead = (playhead + accumulator.advance(FRAME)).min(flight.len() - 1);
+        let next = flight.get(playhead + 1).unwrap_or(&flight[playhead]);
+        black_box(lerp_points(&flight[playhead], next, accumulator.alpha()));
+    }
+    let playback = start.elapsed() / ticks as u32;
+
+    let reintegrate_to = |time: f64| -> TrajectoryPoint {
+        let points = simulate(&ShotParams {
+            max_flight_time: time,
+            ..params
+        })
+        .unwrap();
+        *points.last().unwrap()
+    };
+    let sampled: Vec<usize> = (1..=ticks).step_by(SAMPLE_EVERY).collect();
+    let start = Instant::now();
+    for &tick in &sampled {
+        black_box(reintegrate_to(tick as f64 * FRAME));
+    }
+    let reintegrating = start.elapsed() / sampled.len() as u32;
+
+    println!(
+        "{} points over {:.1} s, precomputed in {:?}; per tick: playback {:?}, \
+         re-integrating {:?}",
+        flight.len(),
+        flight_time,
+        precompute,
+        playback,
+        reintegrating,
+    );
+    assert!(
+        playback < reintegrating,
+        "{playback:?} vs {reintegrating:?}"
+    );
+}
@@ -0,0 +1,113 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+//! Pins what `simulate` gives for a few fixed shots, so a physics change that
+//! moves them shows up here. If the change is meant to, rerun the shots and
+//! update the expected numbers alongside it.
+
+use enigma::ballistics::{impact, landing_point, max_ordinate, simulate, ShotParams};
+use enigma::drag::DragModel;
+
+/// The outputs a shot is pinned by, in meters, seconds and m/s.
+#[derive(Debug)]
+struct Snapshot {
+    range: f64,
+    max_ordinate: f64,
+    time_of_flight: f64,
+    impact_velocity: f64,
+}
+
+/// Relative slack, enough for floating point noise across platforms but far
+/// below any real change to the model.
+const TOLERANCE: f64 = 1e-6;
+
+fn snapshot(params: &ShotParams) -> Snapshot {
+    let points = simulate(params).unwrap();
+    let landing = landing_point(&points).unwrap();
+    Snapshot {
+        range: landing.position.x,
+        max_ordinate: max_ordinate(&points).unwrap().position.y,
+        time_of_flight: landing.time,
+        impact_velocity: impact(&landing, params.mass).velocity,
+    }
+}
+
+fn assert_matches(params: ShotParams, expected: Snapshot) {
+    let actual = snapshot(&params);
+    let fields = [
+        ("range", actual.range, expected.range),
+        ("max ordinate", actual.max_ordinate, expected.max_ordinate),
+        (
+            "time of flight",
+            actual.time_of_flight,
+            expected.time_of_flight,
+        ),
+        (
+            "impact velocity",
+            actual.impact_velocity,
+            expected.impact_velocity,
+        ),
+    ];
+    for (name, actual, expected) in fields {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * TOLERANCE,
+            "{name}: expected {expected}, got {actual}"
+        );
+    }
+}
+
+#[test]
+fn vacuum_lob() {
+    assert_matches(
+        ShotParams {
+            elevation: 30.0,
+            muzzle_velocity: 100.0,
+            drag_enabled: false,
+            ..ShotParams::default()
+        },
+        Snapshot {
+            range: 883.0999723985167,
+            max_ordinate: 127.46451674999811,
+            time_of_flight: 10.197160135712666,
+            impact_velocity: 99.99999022244613,
+        },
+    );
+}
+
+#[test]
+fn g1_308_at_two_degrees() {
+    assert_matches(
+        ShotParams {
+            elevation: 2.0,
+            ..ShotParams::default()
+        },
+        Snapshot {
+            range: 1493.4148806473002,
+            max_ordinate: 20.19293432227992,
+            time_of_flight: 3.8451637853119824,
+            impact_velocity: 249.4405101853903,
+        },
+    );
+}
+
+#[test]
+fn g7_with_wind_and_coriolis() {
+    assert_matches(
+        ShotParams {
+            elevation: 5.0,
+            drag_model: DragModel::G7,
+            ballistic_coefficient: 0.243,
+            muzzle_velocity: 800.0,
+            latitude: 45.0,
+            azimuth: 90.0,
+            wind: 4.0,
+            wind_direction: 90.0,
+            ..ShotParams::default()
+        },
+        Snapshot {
+            range: 2369.021043132007,
+            max_ordinate: 81.03981643533346,
+            time_of_flight: 7.641389989125996,
+            impact_velocity: 198.7063639278807,
+        },
+    );
+}